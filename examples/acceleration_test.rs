@@ -0,0 +1,98 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::Engine,
+    transmission::Transmission,
+    vehicle::Vehicle,
+};
+
+mod common;
+
+fn main() {
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 30.0;
+
+    let mut data_speed = Vec::new();
+    let mut data_rpm = Vec::new();
+    let mut time_to_100 = None;
+
+    let mut total_s = 0.0;
+    let mut last_shift_s = 0.0;
+    while total_s < test_length_s {
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+        vehicle.update(delta_s, 1.0, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        // Give the wheels some time to hook up after a shift, before shifting again
+        if rpm > engine.max_rpm * 0.95 && total_s - last_shift_s > 1.0 && transmission.shift_up() {
+            last_shift_s = total_s;
+        }
+
+        let speed_kmh = vehicle.body.velocity()[0] * 3.6;
+        if time_to_100.is_none() && speed_kmh >= 100.0 {
+            time_to_100 = Some(total_s);
+        }
+        data_speed.push((total_s, speed_kmh));
+        data_rpm.push((total_s, rpm.0));
+
+        total_s += delta_s;
+    }
+
+    match time_to_100 {
+        Some(t) => println!("0-100 km/h: {:.2}s", t),
+        None => println!("Did not reach 100 km/h within {}s", test_length_s),
+    }
+    println!("Distance covered: {:.0}m", vehicle.body.distance());
+
+    let root = BitMapBackend::new("plot_acceleration_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..300.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, 0f32..7000.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Engine rpm")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_speed, &RED)).unwrap()
+        .label("vehicle speed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_secondary_series(LineSeries::new(data_rpm, &BLUE)).unwrap()
+        .label("engine rpm")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> Vehicle {
+    common::vehicle(common::engine())
+}
@@ -0,0 +1,101 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    units::{MetresPerSec, RadPerSec},
+    vehicle::{
+        Vehicle,
+        body::{Body, longitudinal_body::LongitudinalBody},
+    },
+    wheels::brake::Abs,
+};
+
+mod common;
+
+// Stops from 100 km/h with the brake pedal fully pressed, without and with ABS.
+// Without it the wheels lock up and the tyres slide all the way down, with it they are kept
+// just below the slip at which the tyres grip the most. ABS is run at two update rates,
+// which should stop the car in about the same distance
+fn main() {
+    let test_length_s = 8.0;
+
+    let runs = [
+        ("no abs, 60 Hz", 1.0 / 60.0, false, RED),
+        ("abs, 60 Hz", 1.0 / 60.0, true, BLUE),
+        ("abs, 240 Hz", 1.0 / 240.0, true, GREEN),
+    ];
+
+    let root = BitMapBackend::new("plot_brake_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..110.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    for (name, delta_s, abs, color) in runs {
+        let mut vehicle = setup(abs);
+
+        let mut data_speed = Vec::new();
+        let mut stopped_s = None;
+
+        let mut total_s = 0.0;
+        while total_s < test_length_s {
+            vehicle.update(delta_s, 0.0, 1.0, 0.0);
+
+            let speed_kmh = vehicle.body.velocity()[0] * 3.6;
+            if stopped_s.is_none() && speed_kmh <= 0.0 {
+                stopped_s = Some(total_s);
+            }
+            data_speed.push((total_s, speed_kmh));
+
+            total_s += delta_s;
+        }
+
+        match stopped_s {
+            Some(t) => println!("{}: stopped in {:.1}m, {:.2}s", name, vehicle.body.distance(), t),
+            None => println!("{}: still moving after {}s", name, test_length_s),
+        }
+
+        chart
+            .draw_series(LineSeries::new(data_speed, &color)).unwrap()
+            .label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+/// The car rolling at 100 km/h with the clutch pedal pressed
+fn setup(abs: bool) -> Vehicle {
+    let mut vehicle = common::vehicle(common::engine());
+    let speed = MetresPerSec(100.0 / 3.6);
+    vehicle.body = Body::Longitudinal(LongitudinalBody {
+        velocity: speed,
+        ..Default::default()
+    });
+    vehicle.drivetrain.child.pedal_input = 1.0;
+
+    for wheel in vehicle.wheels_mut() {
+        wheel.angular_vel = RadPerSec(speed.0 / wheel.radius);
+        wheel.last_angular_vel = wheel.angular_vel;
+        if abs {
+            wheel.brake.abs = Some(Abs::new(0.12, 4.0, 3.0));
+        }
+    }
+    vehicle
+}
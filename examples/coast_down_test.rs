@@ -0,0 +1,126 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::Engine,
+    transmission::Transmission,
+    vehicle::Vehicle,
+};
+
+mod common;
+
+// Accelerates up to 100 km/h and then lifts off the throttle, once staying in gear and once with the
+// clutch pedal pressed. In gear the fuel is cut and the pumping losses slow the car down much harder,
+// until the engine drops back towards idle and the fuel comes back on
+fn main() {
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 60.0;
+
+    let (data_speed_gear, data_rpm_gear) = coast_down(delta_s, test_length_s, true);
+    let (data_speed_neutral, _) = coast_down(delta_s, test_length_s, false);
+
+    let root = BitMapBackend::new("plot_coast_down_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..150.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, 0f32..7000.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Engine rpm")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_speed_gear, &RED)).unwrap()
+        .label("speed in gear")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_series(LineSeries::new(data_speed_neutral, &GREEN)).unwrap()
+        .label("speed declutched")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
+
+    chart
+        .draw_secondary_series(LineSeries::new(data_rpm_gear, &BLUE)).unwrap()
+        .label("engine rpm in gear")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+/// Points of a line plot, as (time, value)
+type Series = Vec<(f32, f32)>;
+
+/// Runs a single coast down, returning the vehicle speed (km/h) and engine rpm over time
+fn coast_down(delta_s: f32, test_length_s: f32, in_gear: bool) -> (Series, Series) {
+    let mut vehicle = setup();
+
+    let mut data_speed = Vec::new();
+    let mut data_rpm = Vec::new();
+    let mut lift_off_s = None;
+    let mut fuel_cut = false;
+
+    let mut total_s = 0.0;
+    let mut last_shift_s = 0.0;
+    while total_s < test_length_s {
+        let throttle = if lift_off_s.is_some() { 0.0 } else { 1.0 };
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = match lift_off_s {
+            Some(_) if !in_gear => 1.0,
+            _ => 1.0 - total_s / 0.5,
+        };
+        vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        if engine.fuel_cut != fuel_cut {
+            println!("{:.2}s: fuel cut {} at {:.0} rpm", total_s, if engine.fuel_cut { "on" } else { "off" }, rpm.0);
+            fuel_cut = engine.fuel_cut;
+        }
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        if lift_off_s.is_none() && rpm > engine.max_rpm * 0.95 && total_s - last_shift_s > 1.0 && transmission.shift_up() {
+            last_shift_s = total_s;
+        }
+
+        let speed_kmh = vehicle.body.velocity()[0] * 3.6;
+        if lift_off_s.is_none() && speed_kmh >= 100.0 {
+            lift_off_s = Some(total_s);
+        }
+        data_speed.push((total_s, speed_kmh));
+        data_rpm.push((total_s, rpm.0));
+
+        total_s += delta_s;
+    }
+
+    let lift_off_s = lift_off_s.unwrap_or(test_length_s);
+    let final_speed_kmh = data_speed.last().map(|(_, speed)| *speed).unwrap_or(0.0);
+    println!(
+        "Coasting {}: 100 km/h down to {:.1} km/h in {:.1}s",
+        if in_gear { "in gear" } else { "declutched" }, final_speed_kmh, test_length_s - lift_off_s,
+    );
+
+    (data_speed, data_rpm)
+}
+
+fn setup() -> Vehicle {
+    common::vehicle(common::engine())
+}
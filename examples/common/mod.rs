@@ -0,0 +1,232 @@
+// Setup shared between the examples: a rear wheel drive car with a 4.4 litre petrol engine and a six speed manual.
+// Every example only uses part of it, and changes whatever it is testing on top.
+#![allow(dead_code)]
+
+use rust_vehsim::{
+    clutch::Clutch,
+    differential::{
+        Differential,
+        DiffGearing,
+        open_diff::OpenDiff,
+    },
+    engine::{
+        Engine,
+        EngineContainer,
+        combustion_engine::CombustionEngine,
+    },
+    suspension::{Suspension, AntiRollBar},
+    transmission::{
+        Transmission,
+        manual_transmission::{ManualTransmission, Gear},
+    },
+    units::{MetresPerSec, NewtonMetres, Newtons, RadPerSec, Rpm},
+    vehicle::{
+        Vehicle,
+        body::{Body, longitudinal_body::LongitudinalBody},
+        load_transfer::LoadTransfer,
+        steering::Steering,
+    },
+    wheels::{
+        Wheel,
+        Tyre,
+        brake::{Brake, Axle},
+        tyre_model::TyreData,
+    },
+};
+
+pub fn tyre_data() -> TyreData {
+    TyreData {
+        no_load_coeff: 2.08,
+        full_load_coeff: 0.7,
+        load_sensitivity: 0.00023,
+
+        static_friction_coeff: 1.0,
+        sliding_friction_coeff: 1.0,
+        stribeck_velocity: 1.0,
+        stribeck_exponent: 2.0,
+
+        tyre_steepness: 22.0,
+        tyre_amplitude: 3220.0,
+        tyre_falloff: 2700.0,
+    }
+}
+
+pub fn brake(axle: Axle) -> Brake {
+    Brake {
+        max_torque: NewtonMetres(2000.0),
+        max_parking_torque: if axle == Axle::Rear { NewtonMetres(1500.0) } else { NewtonMetres(0.0) },
+        axle,
+        abs: None,
+
+        torque: NewtonMetres(0.0),
+    }
+}
+
+pub fn suspension() -> Suspension {
+    Suspension {
+        spring_rate: 35000.0,
+        bump_damping: 2500.0,
+        rebound_damping: 4000.0,
+        bump_travel: 0.08,
+        droop_travel: 0.12,
+        bump_stop_rate: 500000.0,
+        ride_height: 0.15,
+        tyre_stiffness: 250000.0,
+
+        road_height: 0.0,
+
+        body_height: 0.0,
+        body_vel: MetresPerSec(0.0),
+        wheel_height: 0.0,
+        wheel_vel: MetresPerSec(0.0),
+        normal_force: Newtons(0.0),
+        force: Newtons(0.0),
+    }
+}
+
+pub fn engine() -> CombustionEngine {
+    let torque_curve = vec![
+        (Rpm(1000.0), NewtonMetres(393.0)),
+        (Rpm(1500.0), NewtonMetres(420.0)),
+        (Rpm(2000.0), NewtonMetres(435.0)),
+        (Rpm(2500.0), NewtonMetres(448.0)),
+        (Rpm(3000.0), NewtonMetres(455.0)),
+        (Rpm(3500.0), NewtonMetres(463.0)),
+        (Rpm(4000.0), NewtonMetres(471.0)),
+        (Rpm(4500.0), NewtonMetres(475.0)),
+        (Rpm(5000.0), NewtonMetres(463.0)),
+        (Rpm(5500.0), NewtonMetres(440.0)),
+        (Rpm(5800.0), NewtonMetres(395.0)),
+    ];
+    CombustionEngine {
+        engine_brake_curve: vec![
+            (Rpm(1000.0), NewtonMetres(15.0)),
+            (Rpm(3000.0), NewtonMetres(35.0)),
+            (Rpm(5750.0), NewtonMetres(58.0)),
+        ],
+        ..CombustionEngine::new(torque_curve, Rpm(1100.0), Rpm(5750.0), 0.21)
+    }
+}
+
+pub fn transmission(final_drive: f32, child: Differential) -> ManualTransmission {
+    ManualTransmission {
+        gear_ratios: vec![3.36, 2.07, 1.43, 1.00, 0.84, 0.56],
+        gear_efficiencies: vec![0.97, 0.97, 0.98, 0.99, 0.98, 0.97],
+        reverse_ratio: 3.05,
+        reverse_efficiency: 0.95,
+        final_drive,
+
+        gear: Gear::Forward(1),
+
+        child,
+    }
+}
+
+pub fn clutch(transmission: Transmission) -> Clutch {
+    Clutch {
+        max_torque: NewtonMetres(600.0),
+
+        pedal_input: 1.0,
+
+        locked: false,
+        slip: RadPerSec(0.0),
+        output_angular_vel: RadPerSec(0.0),
+        load_torque: NewtonMetres(0.0),
+        last_ratio: 0.0,
+
+        child: transmission,
+    }
+}
+
+/// The engine driving a single loaded wheel in first gear, with the clutch pedal pressed
+pub fn test_rig(engine: CombustionEngine) -> EngineContainer {
+    let mut wheel = Wheel::new(Tyre::BeamNG(tyre_data()), 1.0, 0.4, 60.0, brake(Axle::Rear));
+    wheel.load = Newtons(3500.0);
+
+    let transmission = transmission(3.42, Differential::WheelConnector(wheel));
+
+    EngineContainer {
+        engine: Engine::CombustionEngine(engine),
+        child: clutch(Transmission::Manual(transmission)),
+    }
+}
+
+/// The driven rear wheels, left first
+pub fn rear_wheels() -> [Wheel; 2] {
+    let mut rear_left = Wheel::new(Tyre::BeamNG(tyre_data()), 1.0, 0.33, 20.0, brake(Axle::Rear));
+    rear_left.suspension = Some(suspension());
+    rear_left.position = [-1.4, 0.78];
+    let mut rear_right = Wheel::new(Tyre::BeamNG(tyre_data()), -1.0, 0.33, 20.0, brake(Axle::Rear));
+    rear_right.suspension = Some(suspension());
+    rear_right.position = [-1.4, -0.78];
+    [rear_left, rear_right]
+}
+
+/// Rear differential gearing, with a little friction in it
+pub fn diff_gearing() -> DiffGearing {
+    DiffGearing {
+        gear_ratio: 3.42,
+        static_friction: NewtonMetres(2.0),
+        variable_friction: 0.01,
+        ..Default::default()
+    }
+}
+
+/// The whole car in first gear, with an open rear differential and a body that only moves in a straight line
+pub fn vehicle(engine: CombustionEngine) -> Vehicle {
+    let [rear_left, rear_right] = rear_wheels();
+    let diff = Differential::OpenDiff(OpenDiff {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: diff_gearing(),
+        side_angular_vels: [RadPerSec(0.0); 2],
+    });
+    vehicle_with_diff(engine, diff)
+}
+
+/// The whole car in first gear, with the given rear differential
+pub fn vehicle_with_diff(engine: CombustionEngine, diff: Differential) -> Vehicle {
+    let mut front_left = Wheel::new(Tyre::BeamNG(tyre_data()), 1.0, 0.33, 20.0, brake(Axle::Front));
+    front_left.suspension = Some(suspension());
+    front_left.position = [1.3, 0.78];
+    let mut front_right = Wheel::new(Tyre::BeamNG(tyre_data()), -1.0, 0.33, 20.0, brake(Axle::Front));
+    front_right.suspension = Some(suspension());
+    front_right.position = [1.3, -0.78];
+
+    let transmission = transmission(1.0, diff);
+
+    Vehicle {
+        mass: 1400.0,
+        drag_coefficient: 0.32,
+        frontal_area: 2.2,
+        air_density: 1.225,
+        rolling_resistance: 0.012,
+        road_grade: 0.0,
+        brake_bias: 0.6,
+        load_transfer: LoadTransfer {
+            cog_height: 0.5,
+            front_roll_distribution: 0.55,
+        },
+        // The driven rear wheels come first in Vehicle::wheels
+        anti_roll_bars: vec![
+            AntiRollBar { stiffness: 20000.0, left: 2, right: 3 },
+            AntiRollBar { stiffness: 10000.0, left: 0, right: 1 },
+        ],
+
+        steering: Steering {
+            max_angle: 0.6,
+            ackermann: 1.0,
+            input: 0.0,
+        },
+
+        body: Body::Longitudinal(LongitudinalBody::default()),
+
+        drivetrain: EngineContainer {
+            engine: Engine::CombustionEngine(engine),
+            child: clutch(Transmission::Manual(transmission)),
+        },
+        free_wheels: vec![front_left, front_right],
+    }
+}
@@ -1,21 +1,18 @@
 use plotters::prelude::*;
 
 use rust_vehsim::{
-    differential::{
-        Differential,
-        welded_diff::WeldedDiff,
-    },
+    differential::Differential,
     engine::{
-        Engine,
         EngineContainer,
         combustion_engine::CombustionEngine,
     },
-    wheels::{
-        Wheel,
-        tyre_model::TyreData,
-    },
+    transmission::Transmission,
+    units::{MetresPerSec, Rpm},
+    wheels::WheelInput,
 };
 
+mod common;
+
 fn main() {
     let mut container = setup();
 
@@ -25,17 +22,18 @@ fn main() {
     let mut data_slip = Vec::new();
     let mut data_wheel_speed = Vec::new();
 
-    let mut fake_veh_speed = 0.0;
+    let mut input = WheelInput::default();
     let mut total_s = 0.0;
     while total_s < test_length_s {
-        container.update(delta_s, fake_veh_speed, 1.0);
+        container.update(delta_s, &input, 1.0);
 
-        if let Differential::WheelConnector(wheel) = &container.child {
+        let Transmission::Manual(transmission) = &container.child.child;
+        if let Differential::WheelConnector(wheel) = &transmission.child {
             data_slip.push((total_s, wheel.last_slip.abs()));
-            data_wheel_speed.push((total_s, wheel.wheel_speed));
+            data_wheel_speed.push((total_s, wheel.wheel_speed.0));
         }
 
-        fake_veh_speed += delta_s * 0.5;
+        input.vehicle_speed += MetresPerSec(delta_s * 0.5);
         total_s += delta_s;
     }
 
@@ -70,7 +68,7 @@ fn main() {
             &RED,
         )).unwrap()
         .label("wheel slip")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
     chart
         .draw_secondary_series(LineSeries::new(
@@ -78,95 +76,22 @@ fn main() {
             &BLUE,
         )).unwrap()
         .label("wheel speed")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw().unwrap();
 
     root.present().unwrap();
 }
 
 fn setup() -> EngineContainer {
-    let td = TyreData {
-        no_load_coeff: 2.08,
-        full_load_coeff: 0.7,
-        load_sensitivity: 0.00023,
-
-        static_friction_coeff: 1.0,
-        sliding_friction_coeff: 1.0,
-        stribeck_velocity: 1.0,
-        stribeck_exponent: 2.0,
-
-        tyre_steepness: 22.0,
-        tyre_amplitude: 3220.0,
-        tyre_falloff: 2700.0,
-    };
-
-    let wheel = Wheel {
-        /// Tyre data
-        tyre: td,
-        /// Determines the wheel direction, to differentiate between left and right wheels
-        direction: 1.0,
-        /// The radius of the wheel, including tyre
-        radius: 0.4,
-        /// The mass of the wheel, including tyre, in kg
-        mass: 60.0,
-
-        /// Deflated, does not imply broken!
-        deflated: false,
-        /// Whether the wheel is still attached to its halfshaft
-        broken: false,
-
-        /// Updated whenever calc_wheel_accel_torque is called
-        last_slip: 0.0,
-
-        last_angular_vel: 0.0,
-
-        angular_vel: 0.0,
-
-        wheel_speed: 0.0,
-    };
-
-    // let diff = Differential::WeldedDiff(WeldedDiff {
-    //     children: [
-    //         Box::new(Differential::WheelConnector(wheel)),
-    //         Box::new(Differential::WheelConnector(wheel)),
-    //     ]
-    // });
-
-    let diff = Differential::WheelConnector(wheel);
-
-    let engine = CombustionEngine {
-        torque_curve: vec![
-            (1000.0, 393.0),
-            (1500.0, 420.0),
-            (2000.0, 435.0),
-            (2500.0, 448.0),
-            (3000.0, 455.0),
-            (3500.0, 463.0),
-            (4000.0, 471.0),
-            (4500.0, 475.0),
-            (5000.0, 463.0),
-            (5500.0, 440.0),
-            (5800.0, 395.0),
-        ],
-        idle_rpm: 1100.0,
-        max_rpm: 5750.0,
-
-        current_rpm: 4500.0,
-
-        inertia: 0.21,
-        static_friction: 8.0,
-        variable_friction: 0.008,
-        variable_friction_mult: 1.0,
-        // engine_brake_torque: 58,
-    };
-
-    EngineContainer {
-        engine: Engine::CombustionEngine(engine),
-        child: diff,
-    }
+    let mut container = common::test_rig(CombustionEngine {
+        current_rpm: Rpm(4500.0),
+        ..common::engine()
+    });
+    container.child.pedal_input = 0.0;
+    container
 }
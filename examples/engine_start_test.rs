@@ -0,0 +1,103 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::{
+        Engine,
+        EngineContainer,
+        combustion_engine::CombustionEngine,
+        ignition::Ignition,
+    },
+    transmission::{
+        Transmission,
+        manual_transmission::Gear,
+    },
+    units::Rpm,
+    wheels::WheelInput,
+};
+
+mod common;
+
+// Starts the engine in neutral and lets it settle at idle. Then, with first gear selected and the car
+// held by the parking brake, the clutch is let out far enough to drag on the engine, which droops and recovers.
+// Finally the clutch is let out completely, which is more than the idle controller can hold, stalling it
+fn main() {
+    let mut container = setup();
+
+    let delta_s = 1.0 / 240.0;
+    let test_length_s = 8.0;
+    let start_s = 0.5;
+    let clutch_drag_s = 3.0;
+    let clutch_drop_s = 6.0;
+
+    let mut data_rpm = Vec::new();
+    let mut last_ignition = Ignition::Off;
+
+    let input = WheelInput {
+        parking_brake: 1.0,
+        ..Default::default()
+    };
+    let mut total_s = 0.0;
+    while total_s < test_length_s {
+        let Engine::CombustionEngine(engine) = &mut container.engine;
+        if total_s >= start_s && engine.ignition == Ignition::Off {
+            engine.start();
+        }
+        if total_s >= clutch_drag_s {
+            let Transmission::Manual(transmission) = &mut container.child.child;
+            transmission.set_gear(Gear::Forward(1));
+            container.child.pedal_input = if total_s >= clutch_drop_s { 0.0 } else { 0.95 };
+        }
+
+        container.update(delta_s, &input, 0.0);
+
+        let Engine::CombustionEngine(engine) = &container.engine;
+        if engine.ignition != last_ignition {
+            println!("{:.2}s: {:?} at {:.0} rpm", total_s, engine.ignition, engine.current_rpm.0);
+            last_ignition = engine.ignition;
+        }
+        data_rpm.push((total_s, engine.current_rpm.0));
+
+        total_s += delta_s;
+    }
+
+    let root = BitMapBackend::new("plot_engine_start.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..2500.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Engine speed (rpm)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_rpm, &RED)).unwrap()
+        .label("rpm")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> EngineContainer {
+    let mut container = common::test_rig(CombustionEngine {
+        ignition: Ignition::Off,
+        current_rpm: Rpm(0.0),
+        ..common::engine()
+    });
+    let Transmission::Manual(transmission) = &mut container.child.child;
+    transmission.gear = Gear::Neutral;
+    container
+}
@@ -0,0 +1,136 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::{
+        Engine,
+        combustion_engine::CombustionEngine,
+        forced_induction::{ForcedInduction, Supercharger, Turbocharger},
+    },
+    transmission::Transmission,
+    units::{NewtonMetres, Rpm},
+    vehicle::Vehicle,
+};
+
+mod common;
+
+// Runs the acceleration test with the same engine naturally aspirated, turbocharged and supercharged.
+// The turbo lags behind at low engine speed and dumps its boost between shifts, while the supercharger
+// boosts straight away, but less at low engine speed and at the cost of the torque it takes to drive it
+fn main() {
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 20.0;
+
+    let turbo = Turbocharger {
+        wastegate_boost: 0.6,
+        spool_rpm: Rpm(3500.0),
+        spool_time: 0.8,
+        blow_off_time: 0.1,
+        efficiency: 0.6,
+
+        boost: 0.0,
+        blow_off: false,
+    };
+    let supercharger = Supercharger {
+        boost_per_rpm: 0.0001,
+        drive_torque: NewtonMetres(40.0),
+        efficiency: 0.6,
+
+        boost: 0.0,
+    };
+    let runs = [
+        ("naturally aspirated", None, RED),
+        ("turbocharged", Some(ForcedInduction::Turbocharger(turbo)), GREEN),
+        ("supercharged", Some(ForcedInduction::Supercharger(supercharger)), BLUE),
+    ];
+
+    let root = BitMapBackend::new("plot_forced_induction_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..250.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, 0f32..1.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Boost (bar)")
+        .draw()
+        .unwrap();
+
+    for (name, forced_induction, color) in runs {
+        let boosted = forced_induction.is_some();
+        let mut vehicle = setup(forced_induction);
+
+        let mut data_speed = Vec::new();
+        let mut data_boost = Vec::new();
+        let mut time_to_100 = None;
+
+        let mut total_s = 0.0;
+        let mut last_shift_s = 0.0;
+        while total_s < test_length_s {
+            // Launch by letting the clutch out over the first half second
+            vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+            // Lift off for a moment while shifting, so the turbo has to spool up again
+            let throttle = if total_s - last_shift_s < 0.3 && last_shift_s > 0.0 { 0.0 } else { 1.0 };
+            vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+            let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+            let rpm = engine.current_rpm;
+            let boost = engine.forced_induction.as_ref().map_or(0.0, |forced_induction| forced_induction.boost());
+            let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+            // Give the wheels some time to hook up after a shift, before shifting again
+            if rpm > engine.max_rpm * 0.95 && total_s - last_shift_s > 1.0 && transmission.shift_up() {
+                last_shift_s = total_s;
+            }
+
+            let speed_kmh = vehicle.body.velocity()[0] * 3.6;
+            if time_to_100.is_none() && speed_kmh >= 100.0 {
+                time_to_100 = Some(total_s);
+            }
+            data_speed.push((total_s, speed_kmh));
+            data_boost.push((total_s, boost));
+
+            total_s += delta_s;
+        }
+
+        match time_to_100 {
+            Some(t) => println!("{}: 0-100 km/h in {:.2}s, {:.0} km/h after {}s", name, t, vehicle.body.velocity()[0] * 3.6, test_length_s),
+            None => println!("{}: did not reach 100 km/h within {}s", name, test_length_s),
+        }
+
+        chart
+            .draw_series(LineSeries::new(data_speed, &color)).unwrap()
+            .label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        if boosted {
+            chart
+                .draw_secondary_series(LineSeries::new(data_boost, color.mix(0.5))).unwrap();
+        }
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup(forced_induction: Option<ForcedInduction>) -> Vehicle {
+    common::vehicle(CombustionEngine {
+        forced_induction,
+        ..common::engine()
+    })
+}
@@ -0,0 +1,144 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::{
+        Engine,
+        combustion_engine::CombustionEngine,
+        fuel::{BsfcMap, FuelConsumption, FuelTank},
+    },
+    transmission::Transmission,
+    units::Rpm,
+    vehicle::Vehicle,
+};
+
+mod common;
+
+// Accelerates gently up to 100 km/h and cruises there, short shifting into top gear, until the tank runs dry.
+// The engine then stops producing torque, stalls once it is dragged below its stall speed, and the car coasts to a stop
+fn main() {
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 1800.0;
+    let cruise_speed = 100.0 / 3.6;
+    let shift_rpm = Rpm(2000.0);
+
+    let mut data_speed = Vec::new();
+    let mut data_fuel = Vec::new();
+    let mut out_of_fuel_s = None;
+
+    let starting_mass = vehicle.total_mass();
+    let mut total_s = 0.0;
+    let mut last_shift_s = 0.0;
+    let mut cruise_fuel = 0.0;
+    let mut cruise_start_m = None;
+    while total_s < test_length_s {
+        let speed = vehicle.body.velocity()[0];
+        let throttle = ((cruise_speed - speed) * 0.5).clamp(0.0, 1.0);
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+        vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        let fuel = engine.fuel_tank.fuel;
+        if out_of_fuel_s.is_none() && engine.fuel_tank.is_empty() {
+            out_of_fuel_s = Some(total_s);
+        }
+        // Measure the consumption once settled into the cruise, until running out
+        if total_s > 60.0 && out_of_fuel_s.is_none() {
+            cruise_start_m.get_or_insert(vehicle.body.distance());
+            cruise_fuel += engine.fuel_flow * delta_s;
+        }
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        if rpm > shift_rpm && total_s - last_shift_s > 1.0 && transmission.shift_up() {
+            last_shift_s = total_s;
+        }
+
+        data_speed.push((total_s, speed * 3.6));
+        data_fuel.push((total_s, fuel));
+
+        total_s += delta_s;
+    }
+
+    match (out_of_fuel_s, cruise_start_m) {
+        (Some(t), Some(start_m)) => {
+            let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+            let cruise_km = (vehicle.body.distance() - start_m) / 1000.0;
+            let cruise_l = cruise_fuel / 1000.0 / engine.fuel_tank.fuel_density;
+            println!("Out of fuel after {:.0}s", t);
+            println!("Cruising consumption: {:.1} l/100km", cruise_l / cruise_km * 100.0);
+        },
+        _ => println!("Did not run out of fuel within {}s", test_length_s),
+    }
+    println!("Distance covered: {:.1}km", vehicle.body.distance() / 1000.0);
+    println!("Mass lost: {:.2}kg", starting_mass - vehicle.total_mass());
+
+    let root = BitMapBackend::new("plot_fuel_consumption_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..150.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, 0f32..2.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Fuel (l)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_speed, &RED)).unwrap()
+        .label("vehicle speed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_secondary_series(LineSeries::new(data_fuel, &BLUE)).unwrap()
+        .label("fuel")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> Vehicle {
+    common::vehicle(CombustionEngine {
+        fuel_consumption: FuelConsumption::Map(BsfcMap {
+            rpm_points: vec![Rpm(1000.0), Rpm(2000.0), Rpm(3000.0), Rpm(4000.0), Rpm(5000.0), Rpm(6000.0)],
+            load_points: vec![0.1, 0.25, 0.5, 0.75, 1.0],
+            bsfc: vec![
+                vec![420.0, 310.0, 250.0, 235.0, 250.0],
+                vec![390.0, 285.0, 230.0, 215.0, 230.0],
+                vec![400.0, 290.0, 235.0, 220.0, 235.0],
+                vec![415.0, 300.0, 245.0, 230.0, 250.0],
+                vec![440.0, 320.0, 260.0, 245.0, 265.0],
+                vec![470.0, 340.0, 275.0, 260.0, 285.0],
+            ],
+        }),
+        // Only a little fuel, so the car runs out within the test
+        fuel_tank: FuelTank {
+            capacity: 60.0,
+            fuel_density: 0.745,
+
+            fuel: 2.0,
+        },
+        ..common::engine()
+    })
+}
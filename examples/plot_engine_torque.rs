@@ -1,6 +1,8 @@
 use plotters::prelude::*;
 
-use rust_vehsim::engine::combustion_engine::CombustionEngine;
+use rust_vehsim::units::Rpm;
+
+mod common;
 
 fn plot<F: FnMut(f32) -> f32, I: Iterator<Item = f32>>(mut f: F, i: I, range_x: (f32, f32), range_y: (f32, f32), name: &str) -> Result<(), Box<dyn std::error::Error>> {
     let plot_name = format!("plot_engine_torque_{}.png", name);
@@ -20,12 +22,12 @@ fn plot<F: FnMut(f32) -> f32, I: Iterator<Item = f32>>(mut f: F, i: I, range_x:
             &RED,
         ))?
         .label("y = x^2")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     root.present()?;
@@ -34,42 +36,18 @@ fn plot<F: FnMut(f32) -> f32, I: Iterator<Item = f32>>(mut f: F, i: I, range_x:
 }
 
 fn main() {
-    let mut engine = CombustionEngine {
-        torque_curve: vec![
-            (1000.0, 393.0),
-            (1500.0, 420.0),
-            (2000.0, 435.0),
-            (2500.0, 448.0),
-            (3000.0, 455.0),
-            (3500.0, 463.0),
-            (4000.0, 471.0),
-            (4500.0, 475.0),
-            (5000.0, 463.0),
-            (5500.0, 440.0),
-            (5800.0, 395.0),
-        ],
-        idle_rpm: 1100.0,
-        max_rpm: 5750.0,
-
-        current_rpm: 0.0,
-
-        inertia: 0.21,
-        static_friction: 8.0,
-        variable_friction: 0.008,
-        variable_friction_mult: 1.0,
-        // engine_brake_torque: 58,
-    };
+    let mut engine = common::engine();
 
     plot(|rpm| {
-        engine.current_rpm = rpm;
+        engine.current_rpm = Rpm(rpm);
         let (torque, _friction_torque) = engine.calc_torque(1.0);
-        torque
-    }, (1100..5750).into_iter().map(|i| i as f32), (0.0, 7000.0), (0.0, 550.0), "torque").expect("Failed to plot!");
+        torque.0
+    }, (1100..5750).map(|i| i as f32), (0.0, 7000.0), (0.0, 550.0), "torque").expect("Failed to plot!");
     plot(|rpm| {
-        engine.current_rpm = rpm;
+        engine.current_rpm = Rpm(rpm);
         let (_torque, friction_torque) = engine.calc_torque(1.0);
-        friction_torque
-    }, (1100..5750).into_iter().map(|i| i as f32), (0.0, 7000.0), (0.0, 550.0), "frictionTorque").expect("Failed to plot!");
+        friction_torque.0
+    }, (1100..5750).map(|i| i as f32), (0.0, 7000.0), (0.0, 550.0), "frictionTorque").expect("Failed to plot!");
 
     let root = BitMapBackend::new(&"plot_engine_torque_throttle.png", (640, 480)).into_drawing_area();
     root.fill(&WHITE).unwrap();
@@ -86,20 +64,20 @@ fn main() {
         chart
             .draw_series(LineSeries::new(
                 (1100..5750).map(|rpm| {
-                    engine.current_rpm = rpm as f32;
+                    engine.current_rpm = Rpm(rpm as f32);
                     let (torque, friction_torque) = engine.calc_torque(throttle);
-                    (rpm, (torque - friction_torque) as i32)
+                    (rpm, (torque - friction_torque).0 as i32)
                 }),
                 &RED,
             )).unwrap()
             .label("y = x^2")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
     }
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw().unwrap();
 
     root.present().unwrap();
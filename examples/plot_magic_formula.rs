@@ -0,0 +1,192 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    units::Newtons,
+    wheels::{
+        Tyre,
+        TyreModel,
+        magic_formula::{Pacejka89, Pacejka94, Mf52},
+    },
+};
+
+// Checks the Magic Formula tyres against what their coefficients say they should do. The peak of each
+// pure slip curve is D, and its slope through zero slip is BCD, the slip stiffness. The horizontal and vertical
+// shifts move the lateral force off zero at zero slip angle, so it crosses zero at about -Sh - Sv / BCD instead.
+// Under combined slip, the force should stay close to the pure slip force when the same amount of slip points another way.
+// The combined forces are plotted for a few slip angles, sweeping the slip ratio from full lock to wheelspin
+fn main() {
+    let load = Newtons(4000.0);
+    let fz = load.0 / 1000.0;
+
+    // Pacejka '89 and '94 take slip in percent and degrees, so their stiffness is converted per unit slip and per radian
+    let pacejka_longitudinal = (1100.0 * fz, 300.0 * fz * 100.0);
+    let pacejka_lateral = (1100.0 * fz, 1100.0 * (2.0 * (fz / 10.0).atan()).sin() * 180.0 / std::f32::consts::PI);
+    // Both Pacejka tyres shift the lateral force by Sh = 0.05 * Fz + 0.1 degrees and Sv = -10 * Fz + 10 N
+    let pacejka_zero_crossing = Some(-(0.05 * fz + 0.1) - (-10.0 * fz + 10.0) / pacejka_lateral.1.to_radians());
+    let mf52 = mf52();
+    let mf52_longitudinal = (mf52.pdx1 * load.0, mf52.pkx1 * load.0);
+    let mf52_lateral = (
+        mf52.pdy1 * load.0,
+        (mf52.pky1 * mf52.fnomin * (2.0 * (load.0 / (mf52.pky2 * mf52.fnomin)).atan()).sin()).abs(),
+    );
+
+    let tyres = [
+        ("pacejka89", Tyre::Pacejka89(pacejka89()), pacejka_longitudinal, pacejka_lateral, pacejka_zero_crossing),
+        ("pacejka94", Tyre::Pacejka94(pacejka94()), pacejka_longitudinal, pacejka_lateral, pacejka_zero_crossing),
+        ("mf52", Tyre::Mf52(mf52), mf52_longitudinal, mf52_lateral, None),
+    ];
+
+    for (name, tyre, (longitudinal_peak, longitudinal_stiffness), (lateral_peak, lateral_stiffness), zero_crossing) in tyres {
+        let slip_ratios = (-1000..=1000).map(|i| i as f32 / 1000.0);
+        let slip_angles = (-1000..=1000).map(|i| i as f32 / 1000.0 * 0.5);
+        let (peak_x, stiffness_x) = curve_shape(|slip| tyre.longitudinal_force(slip, load).0, slip_ratios);
+        let (peak_y, stiffness_y) = curve_shape(|slip| tyre.lateral_force(slip, load).0, slip_angles);
+
+        println!("{}:", name);
+        println!("  longitudinal peak {:.0}N (D {:.0}N), stiffness {:.0}N (BCD {:.0}N)", peak_x, longitudinal_peak, stiffness_x, longitudinal_stiffness);
+        println!("  lateral peak {:.0}N (D {:.0}N), stiffness {:.0}N/rad (BCD {:.0}N/rad)", peak_y, lateral_peak, stiffness_y, lateral_stiffness);
+
+        // Find where the lateral force changes sign, within a few degrees of zero slip angle
+        let crossing = (-5000..5000).map(|i| (i as f32 / 1000.0).to_radians())
+            .find(|slip| tyre.lateral_force(*slip, load).0 < 0.0 && tyre.lateral_force(*slip + 0.001f32.to_radians(), load).0 >= 0.0)
+            .map_or(f32::NAN, |slip| slip.to_degrees());
+        match zero_crossing {
+            Some(expected) => println!("  lateral force crosses zero at {:.2} degrees (expected {:.2} degrees)", crossing, expected),
+            None => println!("  lateral force crosses zero at {:.2} degrees", crossing),
+        }
+
+        let slip_angles_deg = [2.0f32, 4.0, 8.0];
+        let curves: Vec<Vec<(f32, f32)>> = slip_angles_deg.iter().map(|angle| {
+            (-100..=100).map(|i| {
+                let (fx, fy) = tyre.combined_force(i as f32 / 100.0, angle.to_radians(), load);
+                (fx.0, fy.0)
+            }).collect()
+        }).collect();
+
+        // Turning the slip that gives the longitudinal peak halfway towards the side should keep most of the force,
+        // falling short only as far as the tyre is softer sideways
+        let peak_slip = (0..=1000).map(|i| i as f32 / 1000.0)
+            .max_by(|a, b| tyre.longitudinal_force(*a, load).0.total_cmp(&tyre.longitudinal_force(*b, load).0))
+            .unwrap_or(0.0);
+        let diagonal_slip = peak_slip / 2.0f32.sqrt();
+        let (fx, fy) = tyre.combined_force(diagonal_slip, diagonal_slip.atan(), load);
+        let combined = (fx.0 * fx.0 + fy.0 * fy.0).sqrt();
+        println!("  combined force at the peak slip turned 45 degrees {:.0}N, {:.0}% of the longitudinal peak", combined, combined / peak_x * 100.0);
+
+        plot(name, &slip_angles_deg, curves).expect("Failed to plot!");
+    }
+}
+
+/// Returns the peak force of a pure slip curve, halfway between its highest and lowest force so any
+/// vertical shift cancels out, and its slope through zero slip
+fn curve_shape<F: Fn(f32) -> f32, I: Iterator<Item = f32>>(f: F, slips: I) -> (f32, f32) {
+    let (min, max) = slips.map(&f).fold((f32::MAX, f32::MIN), |(min, max), force| (min.min(force), max.max(force)));
+    let step = 0.0001;
+    ((max - min) / 2.0, (f(step) - f(-step)) / (2.0 * step))
+}
+
+fn plot(name: &str, slip_angles_deg: &[f32], curves: Vec<Vec<(f32, f32)>>) -> Result<(), Box<dyn std::error::Error>> {
+    let plot_name = format!("plot_magic_formula_{}.png", name);
+    let root = BitMapBackend::new(&plot_name, (640, 480)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(-6000f32..6000f32, 0f32..6000f32)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Longitudinal force (N)")
+        .y_desc("Lateral force (N)")
+        .draw()?;
+
+    for ((angle, curve), color) in slip_angles_deg.iter().zip(curves).zip([RED, GREEN, BLUE]) {
+        chart
+            .draw_series(LineSeries::new(curve, &color))?
+            .label(format!("{} degrees", angle))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+
+    Ok(())
+}
+
+/// A commonly used set of example coefficients, with a friction coefficient of 1.1 and a small lateral shift
+fn pacejka89() -> Pacejka89 {
+    Pacejka89 {
+        b: [1.5, 0.0, 1100.0, 0.0, 300.0, 0.0, 0.0, 0.0, -2.0, 0.0, 0.0],
+        a: [1.4, 0.0, 1100.0, 1100.0, 10.0, 0.0, 0.0, -2.0, 0.0, 0.05, 0.1, 0.0, -10.0, 10.0],
+    }
+}
+
+/// The same tyre, with its curvature depending on the direction of the slip
+fn pacejka94() -> Pacejka94 {
+    Pacejka94 {
+        b: [1.5, 0.0, 1100.0, 0.0, 300.0, 0.0, 0.0, 0.0, -2.0, 0.0, 0.0, 0.0, 0.0, 0.2],
+        a: [1.4, 0.0, 1100.0, 1100.0, 10.0, 0.0, 0.0, -2.0, 0.05, 0.1, 0.0, -10.0, 10.0, 0.0, 0.0, 0.0, 0.0, 0.2],
+    }
+}
+
+/// A 205/60R15 passenger car tyre
+fn mf52() -> Mf52 {
+    Mf52 {
+        fnomin: 4000.0,
+
+        pcx1: 1.6411,
+        pdx1: 1.1739,
+        pdx2: -0.16395,
+        pex1: 0.46403,
+        pex2: 0.25022,
+        pex3: 0.067842,
+        pex4: -3.7604e-5,
+        pkx1: 22.303,
+        pkx2: 0.48896,
+        pkx3: 0.21253,
+        phx1: 2.1484e-4,
+        phx2: 1.13e-3,
+        pvx1: -1.1107e-5,
+        pvx2: 2.2124e-5,
+
+        rbx1: 13.276,
+        rbx2: -13.778,
+        rcx1: 1.2568,
+        rex1: 0.65225,
+        rex2: -0.24948,
+        rhx1: 5.0722e-3,
+
+        pcy1: 1.3507,
+        pdy1: 1.0489,
+        pdy2: -0.18033,
+        pey1: -7.4722e-3,
+        pey2: -6.3208e-3,
+        pey3: -9.9935,
+        pky1: -21.92,
+        pky2: 2.0012,
+        phy1: 2.6747e-3,
+        phy2: 8.9094e-5,
+        pvy1: 0.037318,
+        pvy2: -0.010049,
+
+        rby1: 7.1433,
+        rby2: 9.1916,
+        rby3: -0.027856,
+        rcy1: 1.0719,
+        rey1: -0.27572,
+        rey2: 0.32802,
+        rhy1: 5.7448e-6,
+        rhy2: -3.1368e-5,
+        rvy1: -0.027825,
+        rvy2: 0.053604,
+        rvy4: 12.12,
+        rvy5: 1.9,
+        rvy6: -10.704,
+    }
+}
@@ -1,6 +1,6 @@
 use plotters::prelude::*;
 
-use rust_vehsim::wheels::tyre_model::TyreData;
+mod common;
 
 fn plot<F: Fn(f32) -> f32, I: Iterator<Item = f32>>(f: F, i: I, range_x: (f32, f32), range_y: (f32, f32), name: &str) -> Result<(), Box<dyn std::error::Error>> {
     let plot_name = format!("plot_tyre_data_{}.png", name);
@@ -20,12 +20,12 @@ fn plot<F: Fn(f32) -> f32, I: Iterator<Item = f32>>(f: F, i: I, range_x: (f32, f
             &RED,
         ))?
         .label("y = x^2")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     root.present()?;
@@ -34,22 +34,11 @@ fn plot<F: Fn(f32) -> f32, I: Iterator<Item = f32>>(f: F, i: I, range_x: (f32, f
 }
 
 fn main() {
-    let td = TyreData {
-        no_load_coeff: 2.08,
-        full_load_coeff: 0.7,
-        load_sensitivity: 0.00023,
-
-        static_friction_coeff: 1.0,
-        sliding_friction_coeff: 1.0,
-        stribeck_velocity: 1.0,
-        stribeck_exponent: 2.0,
-
-        tyre_steepness: 22.0,
-        tyre_amplitude: 3220.0,
-        tyre_falloff: 2700.0,
-    };
-
-    plot(|load| td.calculate_friction_coeff(0.0, load), (0..8000).into_iter().map(|i| i as f32), (0.0, 8000.0), (0.0, 2.0), "load").expect("Failed to plot!");
-    plot(|sliding| td.calculate_friction_coeff(sliding, 0.0), (0..250).into_iter().map(|i| (i as f32) / 10f32), (0.0, 25.0), (0.0, 2.0), "sliding").expect("Failed to plot!");
-    plot(|slip_ratio| td.calculate_accel_force(slip_ratio), (-200..200).into_iter().map(|i| (i as f32) / 100f32), (-2.0, 2.0), (-5000.0, 5000.0), "slip_ratio").expect("Failed to plot!");
+    let td = common::tyre_data();
+
+    plot(|load| td.calculate_friction_coeff(0.0, load), (0..8000).map(|i| i as f32), (0.0, 8000.0), (0.0, 2.0), "load").expect("Failed to plot!");
+    plot(|sliding| td.calculate_friction_coeff(sliding, 0.0), (0..250).map(|i| (i as f32) / 10f32), (0.0, 25.0), (0.0, 2.0), "sliding").expect("Failed to plot!");
+    plot(|slip_ratio| td.calculate_accel_force(slip_ratio), (-200..200).map(|i| (i as f32) / 100f32), (-2.0, 2.0), (-5000.0, 5000.0), "slip_ratio").expect("Failed to plot!");
+    plot(|slip_angle| td.calculate_lateral_force(slip_angle), (-100..100).map(|i| (i as f32) / 100f32), (-1.0, 1.0), (-5000.0, 5000.0), "slip_angle").expect("Failed to plot!");
+    plot(|slip_angle| td.calculate_combined_force(-0.1, slip_angle).1, (-100..100).map(|i| (i as f32) / 100f32), (-1.0, 1.0), (-5000.0, 5000.0), "slip_angle_braking").expect("Failed to plot!");
 }
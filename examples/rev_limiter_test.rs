@@ -0,0 +1,88 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::{
+        Engine,
+        EngineContainer,
+        combustion_engine::CombustionEngine,
+        rev_limiter::{RevLimiter, RevLimiterMode},
+    },
+    units::{MetresPerSec, Rpm},
+    wheels::WheelInput,
+};
+
+mod common;
+
+// Revs the engine against its rev limiter at full throttle, in neutral, once for every limiter mode.
+// The hard and fuel cut limiters bounce the engine speed off the limit, the soft cut settles just below it.
+// Finally launch control holds the engine at a lower speed, as the car stands still
+fn main() {
+    let delta_s = 1.0 / 240.0;
+    let test_length_s = 3.0;
+
+    let launch = RevLimiter::new(RevLimiterMode::FuelCut { hysteresis: Rpm(150.0) }, Some(Rpm(4000.0)), MetresPerSec(1.0));
+    let runs = [
+        ("hard cut", RevLimiter::new(RevLimiterMode::HardCut, None, MetresPerSec(1.0)), RED),
+        ("soft cut", RevLimiter::new(RevLimiterMode::SoftCut { range: Rpm(250.0) }, None, MetresPerSec(1.0)), GREEN),
+        ("fuel cut", RevLimiter::new(RevLimiterMode::FuelCut { hysteresis: Rpm(150.0) }, None, MetresPerSec(1.0)), BLUE),
+        ("launch control", launch, MAGENTA),
+    ];
+
+    let root = BitMapBackend::new("plot_rev_limiter.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..7000.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Engine speed (rpm)")
+        .draw()
+        .unwrap();
+
+    let input = WheelInput::default();
+    for (name, rev_limiter, color) in runs {
+        let mut container = setup(rev_limiter);
+        let mut data_rpm = Vec::new();
+
+        let mut total_s = 0.0;
+        while total_s < test_length_s {
+            container.update(delta_s, &input, 1.0);
+
+            let Engine::CombustionEngine(engine) = &container.engine;
+            data_rpm.push((total_s, engine.current_rpm.0));
+
+            total_s += delta_s;
+        }
+
+        // Once the engine has reached the limiter, it should stay in a narrow band around it
+        let settled = data_rpm.iter().filter(|(t, _)| *t > 1.0).map(|(_, rpm)| *rpm);
+        let (min_rpm, max_rpm) = settled.fold((f32::MAX, f32::MIN), |(min, max), rpm| (min.min(rpm), max.max(rpm)));
+        println!("{}: {:.0} - {:.0} rpm", name, min_rpm, max_rpm);
+
+        chart
+            .draw_series(LineSeries::new(data_rpm, &color)).unwrap()
+            .label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup(rev_limiter: RevLimiter) -> EngineContainer {
+    common::test_rig(CombustionEngine {
+        rev_limiter,
+        ..common::engine()
+    })
+}
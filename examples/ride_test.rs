@@ -0,0 +1,122 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::Engine,
+    transmission::Transmission,
+    vehicle::{
+        Vehicle,
+        body::{Body, rigid_body::RigidBody},
+    },
+};
+
+mod common;
+
+fn main() {
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s: f32 = 20.0;
+
+    // Get up to speed, then drive the left wheels over a 5cm high, 0.5m long bump
+    let target_speed = 50.0 / 3.6;
+    let bump_start = 150.0;
+    let bump_length = 0.5;
+    let bump_height = 0.05;
+
+    let mut data_heave = Vec::new();
+    let mut data_pitch = Vec::new();
+    let mut data_roll = Vec::new();
+
+    let mut total_s = 0.0;
+    while total_s < test_length_s {
+        let Body::RigidBody(body) = &vehicle.body else { unreachable!() };
+        let body_x = body.position[0];
+        for wheel in vehicle.wheels_mut() {
+            let distance = body_x + wheel.position[0] - bump_start;
+            let on_bump = wheel.position[1] > 0.0 && distance > 0.0 && distance < bump_length;
+            if let Some(suspension) = wheel.suspension.as_mut() {
+                suspension.road_height = if on_bump {
+                    bump_height * (distance / bump_length * std::f32::consts::PI).sin()
+                } else {
+                    0.0
+                };
+            }
+        }
+
+        // A simple cruise control to hold the target speed
+        let speed = vehicle.body.speed().0;
+        let throttle = ((target_speed - speed) * 0.5).clamp(0.0, 1.0);
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+        vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        if rpm > engine.max_rpm * 0.95 {
+            transmission.shift_up();
+        }
+
+        let Body::RigidBody(body) = &vehicle.body else { unreachable!() };
+        let [roll, pitch, _] = body.euler_angles();
+        data_heave.push((total_s, body.position[2] * 1000.0));
+        data_pitch.push((total_s, pitch.to_degrees() * 10.0));
+        data_roll.push((total_s, roll.to_degrees() * 10.0));
+
+        total_s += delta_s;
+    }
+
+    let root = BitMapBackend::new("plot_ride_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, -30f32..30.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Heave (mm), pitch and roll (0.1 degrees)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_heave, &RED)).unwrap()
+        .label("heave")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_series(LineSeries::new(data_pitch, &BLUE)).unwrap()
+        .label("pitch")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .draw_series(LineSeries::new(data_roll, &GREEN)).unwrap()
+        .label("roll")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> Vehicle {
+    let mut vehicle = common::vehicle(common::engine());
+    vehicle.body = Body::RigidBody(RigidBody {
+        inertia: [
+            [500.0, 0.0, 0.0],
+            [0.0, 2000.0, 0.0],
+            [0.0, 0.0, 2200.0],
+        ],
+        cog_height: 0.5,
+        ..Default::default()
+    });
+    vehicle
+}
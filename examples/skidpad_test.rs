@@ -0,0 +1,97 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::Engine,
+    transmission::Transmission,
+    vehicle::{
+        Vehicle,
+        body::{Body, planar_body::PlanarBody},
+    },
+};
+
+mod common;
+
+fn main() {
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s: f32 = 40.0;
+
+    // Drive straight up to speed, then slowly turn in and hold the steering on a circle
+    let target_speed = 50.0 / 3.6;
+    let turn_in_s = 10.0;
+    let steering_input = 0.08;
+
+    let mut data_path = Vec::new();
+
+    let mut total_s = 0.0;
+    while total_s < test_length_s {
+        vehicle.steering.input = ((total_s - turn_in_s) / 2.0).clamp(0.0, 1.0) * steering_input;
+
+        // A simple cruise control to hold the target speed
+        let speed = vehicle.body.speed().0;
+        let throttle = ((target_speed - speed) * 0.5).clamp(0.0, 1.0);
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+        vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        if rpm > engine.max_rpm * 0.95 {
+            transmission.shift_up();
+        }
+
+        if let Body::Planar(body) = &vehicle.body {
+            data_path.push((body.position[0], body.position[1]));
+        }
+
+        total_s += delta_s;
+    }
+
+    let [forward_vel, lateral_vel] = vehicle.body.velocity();
+    let yaw_rate = vehicle.body.yaw_rate().0;
+    let [_, lateral_accel] = vehicle.body.acceleration();
+    println!("Speed: {:.1} km/h", vehicle.body.speed().0 * 3.6);
+    println!("Turn radius: {:.1}m", forward_vel / yaw_rate);
+    println!("Lateral acceleration: {:.2}g", lateral_accel / rust_vehsim::GRAVITY);
+    println!("Body slip angle: {:.2} degrees", lateral_vel.atan2(forward_vel).to_degrees());
+
+    let root = BitMapBackend::new("plot_skidpad_test.png", (640, 640)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(-50f32..250.0f32, -50f32..250.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("x (m)")
+        .y_desc("y (m)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_path, &RED)).unwrap()
+        .label("path")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> Vehicle {
+    let mut vehicle = common::vehicle(common::engine());
+    vehicle.body = Body::Planar(PlanarBody {
+        yaw_inertia: 2200.0,
+        ..Default::default()
+    });
+    vehicle
+}
@@ -0,0 +1,181 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    differential::{
+        Differential,
+        open_diff::OpenDiff,
+        welded_diff::WeldedDiff,
+        clutch_lsd::ClutchLsd,
+        viscous_lsd::ViscousLsd,
+        torsen_diff::TorsenDiff,
+    },
+    ground::GroundMaterial,
+    units::{NewtonMetres, RadPerSec},
+    vehicle::Vehicle,
+};
+
+mod common;
+
+// Launches in first gear with the left rear wheel on ice and the right rear wheel on asphalt, once with each
+// kind of differential. The open differential can only put down as much torque on the asphalt as the wheel
+// on ice can take, so that wheel just spins up. The welded differential keeps both wheels together, and sends
+// the torque to the wheel that has the grip. The limited slip differentials land in between:
+// the clutch pack gets its preload onto the asphalt even before the throttle loads up the ramps,
+// the viscous coupling only locks once the wheel on ice has run away from the other one,
+// and the Torsen sends the asphalt up to its bias ratio times what the ice can take
+fn main() {
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 10.0;
+
+    let runs = [
+        ("open", open_diff(), RED),
+        ("welded", welded_diff(), BLUE),
+        ("clutch lsd", clutch_lsd(), GREEN),
+        ("viscous lsd", viscous_lsd(), MAGENTA),
+        ("torsen", torsen_diff(), CYAN),
+    ];
+
+    let root = BitMapBackend::new("plot_split_mu_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..150.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Vehicle speed, wheel speed on ice (km/h)")
+        .draw()
+        .unwrap();
+
+    let mut final_speeds = Vec::new();
+    for (name, diff, color) in runs {
+        let mut vehicle = setup(diff);
+
+        let mut data_speed = Vec::new();
+        let mut data_wheel_speed = Vec::new();
+
+        let mut total_s = 0.0;
+        while total_s < test_length_s {
+            // Launch by letting the clutch out over the first half second
+            vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+            vehicle.update(delta_s, 1.0, 0.0, 0.0);
+
+            data_speed.push((total_s, vehicle.body.velocity()[0] * 3.6));
+            data_wheel_speed.push((total_s, wheel_speed(&vehicle, 0) * 3.6));
+
+            total_s += delta_s;
+        }
+
+        let [left, right] = [wheel_speed(&vehicle, 0), wheel_speed(&vehicle, 1)];
+        println!("{}: {:.0} km/h after {}s, wheel on ice at {:.0} km/h, wheel on asphalt at {:.0} km/h",
+            name, vehicle.body.velocity()[0] * 3.6, test_length_s, left * 3.6, right * 3.6);
+        final_speeds.push((name, vehicle.body.velocity()[0]));
+
+        chart
+            .draw_series(LineSeries::new(data_speed, &color)).unwrap()
+            .label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        chart
+            .draw_series(LineSeries::new(data_wheel_speed, color.mix(0.5))).unwrap();
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+
+    // The limited slip differentials should all land between the open and welded differentials
+    let (_, open) = final_speeds[0];
+    let (_, welded) = final_speeds[1];
+    for &(name, speed) in &final_speeds[2..] {
+        assert!(open < speed && speed < welded, "{} should be faster than open and slower than welded", name);
+    }
+}
+
+/// Speed of the tyre surface of a wheel (m/s), positive when driving forward
+fn wheel_speed(vehicle: &Vehicle, index: usize) -> f32 {
+    let wheel = vehicle.wheels()[index];
+    wheel.angular_vel.surface_speed(wheel.radius).0
+}
+
+fn open_diff() -> Differential {
+    let [rear_left, rear_right] = common::rear_wheels();
+    Differential::OpenDiff(OpenDiff {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: common::diff_gearing(),
+        side_angular_vels: [RadPerSec(0.0); 2],
+    })
+}
+
+fn welded_diff() -> Differential {
+    let [rear_left, rear_right] = common::rear_wheels();
+    Differential::WeldedDiff(WeldedDiff {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: common::diff_gearing(),
+        side_torques: [NewtonMetres(0.0); 2],
+        load_torques: [NewtonMetres(0.0); 2],
+    })
+}
+
+fn clutch_lsd() -> Differential {
+    let [rear_left, rear_right] = common::rear_wheels();
+    Differential::ClutchLsd(ClutchLsd {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: common::diff_gearing(),
+        preload: NewtonMetres(150.0),
+        power_lock_ratio: 0.3,
+        coast_lock_ratio: 0.15,
+        side_angular_vels: [RadPerSec(0.0); 2],
+    })
+}
+
+fn viscous_lsd() -> Differential {
+    let [rear_left, rear_right] = common::rear_wheels();
+    Differential::ViscousLsd(ViscousLsd {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: common::diff_gearing(),
+        viscous_coeff: 40.0,
+        max_locking_torque: NewtonMetres(800.0),
+        side_angular_vels: [RadPerSec(0.0); 2],
+    })
+}
+
+fn torsen_diff() -> Differential {
+    let [rear_left, rear_right] = common::rear_wheels();
+    Differential::TorsenDiff(TorsenDiff {
+        children: [
+            Box::new(Differential::WheelConnector(rear_left)),
+            Box::new(Differential::WheelConnector(rear_right)),
+        ],
+        gearing: common::diff_gearing(),
+        torque_bias_ratio: 3.0,
+        side_angular_vels: [RadPerSec(0.0); 2],
+    })
+}
+
+fn setup(diff: Differential) -> Vehicle {
+    let mut vehicle = common::vehicle_with_diff(common::engine(), diff);
+    // The left rear wheel comes first in Vehicle::wheels
+    vehicle.wheels_mut()[0].surface = GroundMaterial::ICE;
+    vehicle
+}
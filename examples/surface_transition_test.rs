@@ -0,0 +1,136 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::Engine,
+    ground::GroundRegistry,
+    transmission::{Transmission, manual_transmission::Gear},
+    units::{MetresPerSec, RadPerSec},
+    vehicle::{
+        Vehicle,
+        body::{Body, longitudinal_body::LongitudinalBody},
+    },
+};
+
+mod common;
+
+/// Surfaces along the road, as (distance from the start where it begins in m, material name)
+const ROAD: [(f32, &str); 5] = [
+    (0.0, "asphalt"),
+    (40.0, "wet_asphalt"),
+    (80.0, "ice"),
+    (110.0, "gravel"),
+    (160.0, "asphalt"),
+];
+
+// Accelerates at full throttle in fourth gear down a road whose surface keeps changing. Every wheel looks up
+// the surface below its own position, so the front wheels reach each new surface before the rear wheels do.
+// The driven rear wheels grip on asphalt, spin up on the ice, only slowly come back down on the gravel,
+// and hook up again once back on asphalt
+fn main() {
+    let registry = GroundRegistry::default();
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 12.0;
+
+    let mut data_speed = Vec::new();
+    let mut data_wheel_speed = Vec::new();
+    let mut rear_surface = "";
+
+    let mut total_s = 0.0;
+    while total_s < test_length_s {
+        let distance = vehicle.body.distance();
+        let positions: Vec<f32> = vehicle.wheels().iter().map(|wheel| distance + wheel.position[0]).collect();
+        for (index, position) in positions.into_iter().enumerate() {
+            let surface = surface_at(position);
+            assert!(vehicle.set_surface(index, &registry, surface), "Unknown surface {}", surface);
+            // The left rear wheel comes first in Vehicle::wheels
+            if index == 0 && surface != rear_surface {
+                println!("{:.2}s: rear wheels onto {} at {:.0}m, {:.0} km/h", total_s, surface, position, vehicle.body.velocity()[0] * 3.6);
+                rear_surface = surface;
+            }
+        }
+
+        vehicle.update(delta_s, 1.0, 0.0, 0.0);
+
+        let wheel = vehicle.wheels()[0];
+        data_speed.push((vehicle.body.distance(), vehicle.body.velocity()[0] * 3.6));
+        data_wheel_speed.push((vehicle.body.distance(), wheel.angular_vel.surface_speed(wheel.radius).0 * 3.6));
+
+        total_s += delta_s;
+    }
+
+    println!("{:.0} km/h after {}s, {:.0}m down the road", vehicle.body.velocity()[0] * 3.6, test_length_s, vehicle.body.distance());
+
+    let root = BitMapBackend::new("plot_surface_transition_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let max_distance = vehicle.body.distance().max(1.0);
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(0f32..max_distance, 0f32..250.0f32).unwrap();
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .x_desc("Distance (m)")
+        .y_desc("Speed (km/h)")
+        .draw()
+        .unwrap();
+
+    // Mark where each surface begins
+    chart
+        .draw_series(ROAD.iter().skip(1).map(|(start, _)| PathElement::new(vec![(*start, 0.0), (*start, 250.0)], BLACK.mix(0.3))))
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_speed, &RED)).unwrap()
+        .label("vehicle speed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_series(LineSeries::new(data_wheel_speed, &BLUE)).unwrap()
+        .label("rear wheel speed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+/// Name of the surface at a distance down the road
+fn surface_at(position: f32) -> &'static str {
+    ROAD.iter().rev().find(|(start, _)| position >= *start).map(|(_, name)| *name).unwrap_or(ROAD[0].1)
+}
+
+/// The car rolling at 60 km/h in fourth gear, with the clutch out
+fn setup() -> Vehicle {
+    let mut vehicle = common::vehicle(common::engine());
+    let speed = MetresPerSec(60.0 / 3.6);
+    vehicle.body = Body::Longitudinal(LongitudinalBody {
+        velocity: speed,
+        ..Default::default()
+    });
+
+    let mut wheel_angular_vel = RadPerSec(0.0);
+    for wheel in vehicle.wheels_mut() {
+        wheel.angular_vel = RadPerSec(speed.0 / wheel.radius);
+        wheel.last_angular_vel = wheel.angular_vel;
+        wheel_angular_vel = wheel.angular_vel;
+    }
+
+    let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+    transmission.gear = Gear::Forward(4);
+    let ratio = transmission.gear_ratios[3] * transmission.final_drive * common::diff_gearing().gear_ratio;
+    vehicle.drivetrain.child.pedal_input = 0.0;
+    let Engine::CombustionEngine(engine) = &mut vehicle.drivetrain.engine;
+    engine.current_rpm = (wheel_angular_vel * ratio).into();
+    vehicle
+}
@@ -0,0 +1,85 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    units::Newtons,
+    GRAVITY,
+};
+
+mod common;
+
+fn main() {
+    let mut suspension = common::suspension();
+    let sprung_mass = 350.0;
+    let unsprung_mass = 20.0;
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 3.0;
+
+    // A 5cm high, 0.5m long bump, driven over at 36 km/h
+    let speed = 10.0;
+    let bump_start = 5.0;
+    let bump_length = 0.5;
+    let bump_height = 0.05;
+
+    let mut data_force = Vec::new();
+    let mut data_travel = Vec::new();
+
+    let mut total_s = 0.0;
+    while total_s < test_length_s {
+        let distance = total_s * speed - bump_start;
+        suspension.road_height = if distance > 0.0 && distance < bump_length {
+            bump_height * (distance / bump_length * std::f32::consts::PI).sin()
+        } else {
+            0.0
+        };
+
+        let normal_force = suspension.update(delta_s, Newtons(sprung_mass * GRAVITY), sprung_mass, unsprung_mass, Newtons(0.0));
+        data_force.push((total_s, normal_force.0));
+        data_travel.push((total_s, suspension.travel() * 1000.0));
+
+        total_s += delta_s;
+    }
+
+    let root = BitMapBackend::new("plot_suspension_bump_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..10000.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, -60f32..60.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Normal force (N)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Suspension travel (mm)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_force, &RED)).unwrap()
+        .label("normal force")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_secondary_series(LineSeries::new(data_travel, &BLUE)).unwrap()
+        .label("suspension travel")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
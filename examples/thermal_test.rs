@@ -0,0 +1,158 @@
+use plotters::prelude::*;
+
+use rust_vehsim::{
+    engine::{
+        Engine,
+        combustion_engine::CombustionEngine,
+        thermal::{Radiator, ThermalModel, Thermostat},
+    },
+    transmission::{
+        Transmission,
+        manual_transmission::Gear,
+    },
+    units::Rpm,
+    vehicle::Vehicle,
+};
+
+mod common;
+
+// Starts with a cold engine and cruises at 100 km/h, while the oil warms up and the friction comes down.
+// Once the coolant is warm the thermostat opens and holds it at temperature. Then the road turns into
+// a long steep climb in third gear, which makes more heat than the radiator can get rid of, until the
+// engine derates itself to keep from overheating
+fn main() {
+    let mut vehicle = setup();
+
+    let delta_s = 1.0 / 60.0;
+    let test_length_s = 1200.0;
+    let climb_s = 600.0;
+    let cruise_speed = 100.0 / 3.6;
+    let shift_rpm = Rpm(2500.0);
+
+    let mut data_coolant = Vec::new();
+    let mut data_oil = Vec::new();
+    let mut data_speed = Vec::new();
+    let mut derating = false;
+
+    let mut total_s = 0.0;
+    let mut last_shift_s = 0.0;
+    while total_s < test_length_s {
+        if total_s >= climb_s && vehicle.road_grade == 0.0 {
+            vehicle.road_grade = 0.1;
+            let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+            transmission.set_gear(Gear::Forward(3));
+        }
+
+        let speed = vehicle.body.velocity()[0];
+        let throttle = ((cruise_speed - speed) * 0.5).clamp(0.0, 1.0);
+        // Launch by letting the clutch out over the first half second
+        vehicle.drivetrain.child.pedal_input = 1.0 - total_s / 0.5;
+        vehicle.update(delta_s, throttle, 0.0, 0.0);
+
+        let Engine::CombustionEngine(engine) = &vehicle.drivetrain.engine;
+        let rpm = engine.current_rpm;
+        let thermal = engine.thermal.expect("The engine should have a thermal model");
+        if (thermal.torque_fraction() < 1.0) != derating {
+            derating = !derating;
+            println!("{:.0}s: derating {} at {:.1} C", total_s, if derating { "started" } else { "stopped" }, thermal.coolant_temp);
+        }
+        if (total_s / 60.0).fract() < delta_s / 60.0 {
+            println!(
+                "{:.0}s: coolant {:.1} C, oil {:.1} C, friction x{:.2}, {:.0} km/h",
+                total_s, thermal.coolant_temp, thermal.oil_temp, thermal.friction_multiplier(), speed * 3.6,
+            );
+        }
+        let Transmission::Manual(transmission) = &mut vehicle.drivetrain.child.child;
+        if total_s < climb_s && rpm > shift_rpm && total_s - last_shift_s > 1.0 && transmission.shift_up() {
+            last_shift_s = total_s;
+        }
+
+        data_coolant.push((total_s, thermal.coolant_temp));
+        data_oil.push((total_s, thermal.oil_temp));
+        data_speed.push((total_s, speed * 3.6));
+
+        total_s += delta_s;
+    }
+
+    let root = BitMapBackend::new("plot_thermal_test.png", (640, 480)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root)
+        .margin(5)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .right_y_label_area_size(30)
+        .build_cartesian_2d(0f32..test_length_s, 0f32..150.0f32).unwrap()
+        .set_secondary_coord(0f32..test_length_s, 0f32..150.0f32);
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .disable_y_mesh()
+        .y_desc("Temperature (C)")
+        .draw()
+        .unwrap();
+
+    chart
+        .configure_secondary_axes()
+        .y_desc("Vehicle speed (km/h)")
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(data_coolant, &RED)).unwrap()
+        .label("coolant")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_series(LineSeries::new(data_oil, &GREEN)).unwrap()
+        .label("oil")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
+
+    chart
+        .draw_secondary_series(LineSeries::new(data_speed, &BLUE)).unwrap()
+        .label("vehicle speed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw().unwrap();
+
+    root.present().unwrap();
+}
+
+fn setup() -> Vehicle {
+    common::vehicle(CombustionEngine {
+        thermal: Some(ThermalModel {
+            coolant_heat_capacity: 120_000.0,
+            oil_heat_capacity: 10_000.0,
+            combustion_heat_ratio: 1.0,
+            oil_conductance: 800.0,
+            passive_conductance: 20.0,
+            radiator: Radiator {
+                base_conductance: 150.0,
+                conductance_per_speed: 20.0,
+            },
+            thermostat: Thermostat {
+                opening_temp: 85.0,
+                fully_open_temp: 95.0,
+            },
+            ambient_temp: 20.0,
+
+            cold_friction_mult: 2.0,
+            cold_oil_temp: 20.0,
+            warm_oil_temp: 90.0,
+
+            derate_range: Some((110.0, 125.0)),
+            damage_temp: Some(130.0),
+
+            // Cold start
+            coolant_temp: 20.0,
+            oil_temp: 20.0,
+            damaged: false,
+        }),
+        ..common::engine()
+    })
+}
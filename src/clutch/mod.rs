@@ -0,0 +1,86 @@
+// A simple friction clutch, sitting between the engine and the transmission.
+// The clutch transmits whatever torque makes both sides spin together, as long as
+// that fits within its (pedal dependent) capacity. Otherwise it slips, and transmits
+// its full capacity in the direction that brings both sides closer together.
+
+use crate::units::{NewtonMetres, RadPerSec};
+
+pub struct Clutch {
+    /// Maximum torque the clutch can transmit when fully engaged
+    pub max_torque: NewtonMetres,
+
+    /// Clutch pedal input, 0 is fully engaged, 1 is fully disengaged
+    pub pedal_input: f32,
+
+    /// Whether both sides of the clutch are currently spinning together
+    pub locked: bool,
+    /// Speed difference between the engine side and the transmission side
+    pub slip: RadPerSec,
+    /// Angular velocity of the transmission side, updated whenever update is called
+    pub output_angular_vel: RadPerSec,
+    /// Torque the drivetrain pushes back with on top of its inertia, like the tyres and friction,
+    /// estimated from how it responded during the last update
+    pub load_torque: NewtonMetres,
+    /// Transmission ratio during the last update, to notice gear changes
+    pub last_ratio: f32,
+
+    pub child: crate::transmission::Transmission,
+}
+
+impl Clutch {
+    /// The maximum torque the clutch can currently transmit, based on the pedal input
+    pub fn capacity(&self) -> NewtonMetres {
+        self.max_torque * (1.0 - self.pedal_input.clamp(0.0, 1.0))
+    }
+
+    /// The torque that makes the engine and transmission accelerate together, while pulling
+    /// any remaining speed difference closed within a single update.
+    /// This is also the reaction torque the drivetrain puts back on the engine
+    pub fn locked_torque(&self, delta_s: f32, engine_angular_vel: RadPerSec, engine_torque: NewtonMetres, engine_inertia: f32) -> NewtonMetres {
+        let drivetrain_inertia = self.child.effective_inertia();
+        if drivetrain_inertia <= 0.0 {
+            return NewtonMetres(0.0);
+        }
+        let slip = (engine_angular_vel - self.output_angular_vel).0;
+        NewtonMetres((slip / delta_s + engine_torque.0 / engine_inertia - self.load_torque.0 / drivetrain_inertia) / (1.0 / engine_inertia + 1.0 / drivetrain_inertia))
+    }
+
+    /// Returns the torque transmitted from the engine to the transmission
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, engine_angular_vel: RadPerSec, engine_torque: NewtonMetres, engine_inertia: f32) -> NewtonMetres {
+        // Nothing to drive against in neutral, so let the engine spin freely
+        if self.child.in_neutral() {
+            self.child.update(delta_s, input, NewtonMetres(0.0));
+            self.locked = false;
+            self.slip = RadPerSec(0.0);
+            self.output_angular_vel = engine_angular_vel;
+            self.load_torque = NewtonMetres(0.0);
+            self.last_ratio = 0.0;
+            return NewtonMetres(0.0);
+        }
+
+        // The transmission side speed from the last update is only valid in the gear it was measured in.
+        // After a shift, or coming out of neutral, the input shaft turns at a different speed right away,
+        // and the load estimated in the old gear no longer applies either
+        self.output_angular_vel = self.child.input_angular_vel();
+        let ratio = self.child.current_ratio();
+        if ratio != self.last_ratio {
+            self.load_torque = NewtonMetres(0.0);
+            self.last_ratio = ratio;
+        }
+
+        let capacity = self.capacity();
+        self.slip = engine_angular_vel - self.output_angular_vel;
+
+        // Slipping at full capacity would make both sides overshoot each other, whenever less torque is enough to lock up
+        let locked_torque = self.locked_torque(delta_s, engine_angular_vel, engine_torque, engine_inertia);
+        self.locked = locked_torque.abs() <= capacity;
+        let torque = locked_torque.clamp(-capacity, capacity);
+
+        let last_output_angular_vel = self.output_angular_vel;
+        self.output_angular_vel = self.child.update(delta_s, input, torque);
+        let output_angular_accel = (self.output_angular_vel - last_output_angular_vel).0 / delta_s;
+        self.load_torque = NewtonMetres(output_angular_accel * self.child.effective_inertia()) - torque;
+
+        torque
+    }
+}
@@ -0,0 +1,41 @@
+// A clutch-pack limited slip differential.
+// The clutch pack is clamped by a spring preload, plus a portion of the input torque
+// through the ramp angles, which differ between power (accelerating) and coast (engine braking).
+
+use crate::units::{NewtonMetres, RadPerSec};
+
+pub struct ClutchLsd {
+    pub children: [Box<super::Differential>; 2],
+
+    pub gearing: super::DiffGearing,
+
+    /// Locking torque that is always available, even without any input torque
+    pub preload: NewtonMetres,
+    /// Fraction of the input torque available as locking torque under power (0-1)
+    pub power_lock_ratio: f32,
+    /// Fraction of the input torque available as locking torque while coasting (0-1)
+    pub coast_lock_ratio: f32,
+
+    /// Angular velocity of each side, updated whenever update is called
+    pub side_angular_vels: [RadPerSec; 2],
+}
+
+impl ClutchLsd {
+    pub fn locking_torque(&self, torque_in: NewtonMetres) -> NewtonMetres {
+        let lock_ratio = if torque_in.0 >= 0.0 { self.power_lock_ratio } else { self.coast_lock_ratio };
+        self.preload + torque_in.abs() * lock_ratio
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let torque_in = self.gearing.carrier_torque(torque_in);
+        let speed_difference = self.side_angular_vels[0] - self.side_angular_vels[1];
+        let (output_a, output_b) = super::split_torque(torque_in, self.locking_torque(torque_in), speed_difference);
+
+        let angular_vel_a = self.children[0].update(delta_s, input, output_a);
+        let angular_vel_b = self.children[1].update(delta_s, input, output_b);
+        self.side_angular_vels = [angular_vel_a, angular_vel_b];
+
+        self.gearing.input_angular_vel((angular_vel_a + angular_vel_b) / 2.0)
+    }
+}
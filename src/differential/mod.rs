@@ -1,16 +1,199 @@
 pub mod welded_diff;
+pub mod open_diff;
+pub mod clutch_lsd;
+pub mod viscous_lsd;
+pub mod torsen_diff;
 
+use crate::units::{NewtonMetres, RadPerSec};
+
+/// Speed difference between both sides (rad/s) at which a limited slip differential
+/// applies its full locking torque. Below this, the locking torque is scaled down
+/// to prevent it from flipping direction every update.
+const FULL_LOCK_SPEED_DIFFERENCE: f32 = 0.5;
+
+/// Input speed (rad/s) at which the static friction of a differential is fully applied.
+/// Below this, it is scaled down so it cannot reverse the rotation at standstill.
+const FULL_STATIC_FRICTION_SPEED: f32 = 0.5;
+
+/// Gear ratio and internal friction of a differential
+#[derive(Debug, Copy, Clone)]
+pub struct DiffGearing {
+    /// Ratio between the input shaft and the carrier. Torque is multiplied by it, speed is divided by it
+    pub gear_ratio: f32,
+    /// Friction torque that is always applied while spinning, regardless of speed
+    pub static_friction: NewtonMetres,
+    /// Friction torque that goes up with the input speed (Nm per rad/s)
+    pub variable_friction: f32,
+
+    /// Angular velocity of the input shaft, updated whenever the differential is updated
+    pub input_angular_vel: RadPerSec,
+    /// Power lost to friction during the last update (W)
+    pub dissipated_power: f32,
+}
+
+impl Default for DiffGearing {
+    fn default() -> Self {
+        Self {
+            gear_ratio: 1.0,
+            static_friction: NewtonMetres(0.0),
+            variable_friction: 0.0,
+
+            input_angular_vel: RadPerSec(0.0),
+            dissipated_power: 0.0,
+        }
+    }
+}
+
+impl DiffGearing {
+    pub fn friction_torque(&self) -> NewtonMetres {
+        let angular_vel = self.input_angular_vel.0;
+        self.static_friction * (angular_vel / FULL_STATIC_FRICTION_SPEED).clamp(-1.0, 1.0) + NewtonMetres(self.variable_friction * angular_vel)
+    }
+
+    /// Returns the torque delivered to the carrier
+    fn carrier_torque(&mut self, torque_in: NewtonMetres) -> NewtonMetres {
+        let friction_torque = self.friction_torque();
+        self.dissipated_power = (friction_torque * self.input_angular_vel).abs();
+        (torque_in - friction_torque) * self.gear_ratio
+    }
+
+    /// Returns the angular velocity of the input shaft
+    fn input_angular_vel(&mut self, carrier_angular_vel: RadPerSec) -> RadPerSec {
+        self.input_angular_vel = carrier_angular_vel * self.gear_ratio;
+        self.input_angular_vel
+    }
+}
+
+// Wheels are kept inline, they are leaves of the drivetrain and there are only a handful of them
+#[allow(clippy::large_enum_variant)]
 pub enum Differential {
     WheelConnector(crate::wheels::Wheel),
     WeldedDiff(welded_diff::WeldedDiff),
+    OpenDiff(open_diff::OpenDiff),
+    ClutchLsd(clutch_lsd::ClutchLsd),
+    ViscousLsd(viscous_lsd::ViscousLsd),
+    TorsenDiff(torsen_diff::TorsenDiff),
 }
 
 impl Differential {
-    /// Returns the angular velocity of its children
-    pub fn update(&mut self, delta_s: f32, vehicle_speed: f32, torque_in: f32) -> f32 {
+    /// Returns the angular velocity of its input
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        match self {
+            Self::WheelConnector(wheel) => wheel.update(delta_s, input, torque_in),
+            Self::WeldedDiff(diff) => diff.update(delta_s, input, torque_in),
+            Self::OpenDiff(diff) => diff.update(delta_s, input, torque_in),
+            Self::ClutchLsd(diff) => diff.update(delta_s, input, torque_in),
+            Self::ViscousLsd(diff) => diff.update(delta_s, input, torque_in),
+            Self::TorsenDiff(diff) => diff.update(delta_s, input, torque_in),
+        }
+    }
+
+    /// Angular velocity of its input as of the last update
+    pub fn angular_vel(&self) -> RadPerSec {
+        match self {
+            Self::WheelConnector(wheel) => wheel.angular_vel,
+            _ => self.gearing().map_or(RadPerSec(0.0), |gearing| gearing.input_angular_vel),
+        }
+    }
+
+    /// Gear ratio and friction of this differential, None for a wheel
+    pub fn gearing(&self) -> Option<&DiffGearing> {
+        match self {
+            Self::WheelConnector(_) => None,
+            Self::WeldedDiff(diff) => Some(&diff.gearing),
+            Self::OpenDiff(diff) => Some(&diff.gearing),
+            Self::ClutchLsd(diff) => Some(&diff.gearing),
+            Self::ViscousLsd(diff) => Some(&diff.gearing),
+            Self::TorsenDiff(diff) => Some(&diff.gearing),
+        }
+    }
+
+    /// The differentials or wheels connected to this differential
+    pub fn children(&self) -> &[Box<Differential>] {
+        match self {
+            Self::WheelConnector(_) => &[],
+            Self::WeldedDiff(diff) => &diff.children,
+            Self::OpenDiff(diff) => &diff.children,
+            Self::ClutchLsd(diff) => &diff.children,
+            Self::ViscousLsd(diff) => &diff.children,
+            Self::TorsenDiff(diff) => &diff.children,
+        }
+    }
+
+    pub fn children_mut(&mut self) -> &mut [Box<Differential>] {
         match self {
-            Self::WheelConnector(wheel) => wheel.update(delta_s, vehicle_speed, torque_in),
-            Self::WeldedDiff(diff) => diff.update(delta_s, vehicle_speed, torque_in),
+            Self::WheelConnector(_) => &mut [],
+            Self::WeldedDiff(diff) => &mut diff.children,
+            Self::OpenDiff(diff) => &mut diff.children,
+            Self::ClutchLsd(diff) => &mut diff.children,
+            Self::ViscousLsd(diff) => &mut diff.children,
+            Self::TorsenDiff(diff) => &mut diff.children,
         }
     }
+
+    /// All wheels connected to this differential, depth first, starting at the first child
+    pub fn wheels(&self) -> Vec<&crate::wheels::Wheel> {
+        match self {
+            Self::WheelConnector(wheel) => vec![wheel],
+            _ => self.children().iter().flat_map(|child| child.wheels()).collect(),
+        }
+    }
+
+    /// All wheels connected to this differential, depth first, starting at the first child
+    pub fn wheels_mut(&mut self) -> Vec<&mut crate::wheels::Wheel> {
+        match self {
+            Self::WheelConnector(wheel) => vec![wheel],
+            _ => self.children_mut().iter_mut().flat_map(|child| child.wheels_mut()).collect(),
+        }
+    }
+
+    /// Inertia of everything behind the input, without the tyres
+    pub fn inertia(&self) -> f32 {
+        match self {
+            Self::WheelConnector(wheel) => wheel.inertia(),
+            _ => self.input_inertia(Self::inertia),
+        }
+    }
+
+    /// Inertia felt at the input during the last update, including the tyres gripping the road
+    pub fn effective_inertia(&self) -> f32 {
+        match self {
+            Self::WheelConnector(wheel) => wheel.effective_inertia,
+            _ => self.input_inertia(Self::effective_inertia),
+        }
+    }
+
+    /// Combines the inertia of both sides, as given by side_inertia, into the inertia felt at the input
+    fn input_inertia(&self, side_inertia: fn(&Self) -> f32) -> f32 {
+        let ratio = self.gearing().map(|gearing| gearing.gear_ratio).unwrap_or(1.0);
+        let [inertia_a, inertia_b] = match self.children() {
+            [a, b] => [side_inertia(a), side_inertia(b)],
+            _ => [0.0; 2],
+        };
+        let total_inertia = inertia_a + inertia_b;
+        match self {
+            // Both sides turn as one
+            Self::WeldedDiff(_) => total_inertia / (ratio * ratio),
+            // Nothing to spin behind the differential
+            _ if total_inertia == 0.0 => 0.0,
+            // Both sides get the same torque while the carrier turns at their average speed,
+            // so the input mostly feels the lighter side, which spins up first
+            _ => 4.0 * inertia_a * inertia_b / total_inertia / (ratio * ratio),
+        }
+    }
+
+    /// Returns the power lost to friction during the last update, including all nested differentials
+    pub fn dissipated_power(&self) -> f32 {
+        let own = self.gearing().map(|gearing| gearing.dissipated_power).unwrap_or(0.0);
+        own + self.children().iter().map(|child| child.dissipated_power()).sum::<f32>()
+    }
+}
+
+/// Splits the input torque evenly, then transfers up to locking_torque from the faster to the slower side.
+/// speed_difference is the angular velocity of side a minus that of side b.
+/// Returns the torque for side a and side b
+fn split_torque(torque_in: NewtonMetres, locking_torque: NewtonMetres, speed_difference: RadPerSec) -> (NewtonMetres, NewtonMetres) {
+    let transfer = locking_torque * (speed_difference.0 / FULL_LOCK_SPEED_DIFFERENCE).clamp(-1.0, 1.0);
+    let half = torque_in * 0.5;
+    (half - transfer * 0.5, half + transfer * 0.5)
 }
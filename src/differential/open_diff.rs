@@ -0,0 +1,36 @@
+// An open differential. Torque is always split evenly between both sides, while the carrier
+// spins at the average speed of both sides, allowing them to spin at different speeds.
+// Because the split is always equal, the side with the least traction limits how much torque
+// can be put down: once it breaks loose it spins up, dragging the carrier speed (and with it
+// the engine) along, while the other side never receives more torque than the spinning side.
+
+use crate::units::{NewtonMetres, RadPerSec};
+
+pub struct OpenDiff {
+    pub children: [Box<super::Differential>; 2],
+
+    pub gearing: super::DiffGearing,
+
+    /// Angular velocity of each side, updated whenever update is called
+    pub side_angular_vels: [RadPerSec; 2],
+}
+
+impl OpenDiff {
+    /// Difference in angular velocity between both sides
+    pub fn speed_difference(&self) -> RadPerSec {
+        self.side_angular_vels[0] - self.side_angular_vels[1]
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let torque_in = self.gearing.carrier_torque(torque_in);
+        let output = torque_in * 0.5;
+
+        let angular_vel_a = self.children[0].update(delta_s, input, output);
+        let angular_vel_b = self.children[1].update(delta_s, input, output);
+        self.side_angular_vels = [angular_vel_a, angular_vel_b];
+
+        // Kinematic constraint of the spider gears
+        self.gearing.input_angular_vel((angular_vel_a + angular_vel_b) / 2.0)
+    }
+}
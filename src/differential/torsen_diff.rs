@@ -0,0 +1,38 @@
+// A Torsen (torque sensing) differential.
+// The worm gears allow the slower side to receive at most torque_bias_ratio times
+// the torque of the faster side. Like an open diff, it cannot send any torque to the
+// slower side when the faster side has no traction at all.
+
+use crate::units::{NewtonMetres, RadPerSec};
+
+pub struct TorsenDiff {
+    pub children: [Box<super::Differential>; 2],
+
+    pub gearing: super::DiffGearing,
+
+    /// Maximum ratio between the torque on the slower and the faster side (>= 1)
+    pub torque_bias_ratio: f32,
+
+    /// Angular velocity of each side, updated whenever update is called
+    pub side_angular_vels: [RadPerSec; 2],
+}
+
+impl TorsenDiff {
+    pub fn locking_torque(&self, torque_in: NewtonMetres) -> NewtonMetres {
+        let tbr = self.torque_bias_ratio.max(1.0);
+        torque_in.abs() * (tbr - 1.0) / (tbr + 1.0)
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let torque_in = self.gearing.carrier_torque(torque_in);
+        let speed_difference = self.side_angular_vels[0] - self.side_angular_vels[1];
+        let (output_a, output_b) = super::split_torque(torque_in, self.locking_torque(torque_in), speed_difference);
+
+        let angular_vel_a = self.children[0].update(delta_s, input, output_a);
+        let angular_vel_b = self.children[1].update(delta_s, input, output_b);
+        self.side_angular_vels = [angular_vel_a, angular_vel_b];
+
+        self.gearing.input_angular_vel((angular_vel_a + angular_vel_b) / 2.0)
+    }
+}
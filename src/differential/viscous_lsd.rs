@@ -0,0 +1,38 @@
+// A viscous limited slip differential.
+// The locking torque comes from shearing fluid between the plates, so it only
+// builds up once both sides spin at different speeds.
+
+use crate::units::{NewtonMetres, RadPerSec};
+
+pub struct ViscousLsd {
+    pub children: [Box<super::Differential>; 2],
+
+    pub gearing: super::DiffGearing,
+
+    /// Locking torque per unit of speed difference between both sides (Nm per rad/s)
+    pub viscous_coeff: f32,
+    /// Maximum locking torque the coupling can produce
+    pub max_locking_torque: NewtonMetres,
+
+    /// Angular velocity of each side, updated whenever update is called
+    pub side_angular_vels: [RadPerSec; 2],
+}
+
+impl ViscousLsd {
+    pub fn locking_torque(&self, speed_difference: RadPerSec) -> NewtonMetres {
+        NewtonMetres(speed_difference.0.abs() * self.viscous_coeff).min(self.max_locking_torque)
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let torque_in = self.gearing.carrier_torque(torque_in);
+        let speed_difference = self.side_angular_vels[0] - self.side_angular_vels[1];
+        let (output_a, output_b) = super::split_torque(torque_in, self.locking_torque(speed_difference), speed_difference);
+
+        let angular_vel_a = self.children[0].update(delta_s, input, output_a);
+        let angular_vel_b = self.children[1].update(delta_s, input, output_b);
+        self.side_angular_vels = [angular_vel_a, angular_vel_b];
+
+        self.gearing.input_angular_vel((angular_vel_a + angular_vel_b) / 2.0)
+    }
+}
@@ -1,18 +1,52 @@
-// TODO: Support friction in the diff
-// TODO: Support diff gear ratio
+// A welded differential, or spool. Both sides are locked together and always spin at the same speed,
+// so the spool and everything behind it turns as one rigid body. The input torque is split however
+// it takes to keep both sides together, so the side with more grip can take all of it.
+// Like the clutch, the torque each side pushes back with (the tyres, brakes and friction) is
+// estimated from how that side responded during the last update. It is taken at the speed the side
+// ended up at, so a tyre that just hooked up already pushes back with its new grip.
+
+use crate::units::{NewtonMetres, RadPerSec};
+
 pub struct WeldedDiff {
     pub children: [Box<super::Differential>; 2],
+
+    pub gearing: super::DiffGearing,
+
+    /// Torque delivered to each side, updated whenever update is called
+    pub side_torques: [NewtonMetres; 2],
+    /// Torque each side pushes back with on top of its inertia, estimated from the last update
+    pub load_torques: [NewtonMetres; 2],
 }
 
 impl WeldedDiff {
-    /// Returns the angular velocity of its children
-    pub fn update(&mut self, delta_s: f32, vehicle_speed: f32, torque_in: f32) -> f32 {
-        let output_a = torque_in * 0.5;
-        let output_b = torque_in * 0.5;
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let torque_in = self.gearing.carrier_torque(torque_in);
+        let inertias = [self.children[0].effective_inertia(), self.children[1].effective_inertia()];
+        let angular_vels = [self.children[0].angular_vel(), self.children[1].angular_vel()];
+        let total_inertia = inertias[0] + inertias[1];
+
+        // Accelerate both sides as one body, with their combined inertia and load.
+        // Any speed difference left between them is pulled closed within this update
+        let momentum = inertias[0] * angular_vels[0].0 + inertias[1] * angular_vels[1].0;
+        let total_torque = torque_in + self.load_torques[0] + self.load_torques[1];
+        let spool_angular_vel = if total_inertia > 0.0 {
+            RadPerSec((momentum + total_torque.0 * delta_s) / total_inertia)
+        } else {
+            // Nothing to spin, so the sides just meet in the middle
+            (angular_vels[0] + angular_vels[1]) / 2.0
+        };
 
-        let rpm_a = self.children[0].update(delta_s, vehicle_speed, output_a);
-        let rpm_b = self.children[1].update(delta_s, vehicle_speed, output_b);
+        for i in 0..2 {
+            self.side_torques[i] = NewtonMetres(inertias[i] * (spool_angular_vel - angular_vels[i]).0 / delta_s) - self.load_torques[i];
+            let new_angular_vel = self.children[i].update(delta_s, input, self.side_torques[i]);
+            // Whatever the torque given to the side does not account for is its load. Measured against its
+            // bare inertia, that is the load at its new speed, with the tyre stiffness already felt
+            self.load_torques[i] = NewtonMetres(self.children[i].inertia() * (new_angular_vel - angular_vels[i]).0 / delta_s) - self.side_torques[i];
+        }
 
-        (rpm_a + rpm_b) / 2.0
+        let angular_vel_a = self.children[0].angular_vel();
+        let angular_vel_b = self.children[1].angular_vel();
+        self.gearing.input_angular_vel((angular_vel_a + angular_vel_b) / 2.0)
     }
 }
@@ -1,73 +1,279 @@
+use crate::units::{MetresPerSec, NewtonMetres, RadPerSec, Rpm};
+
+use super::{
+    CLOSED_THROTTLE,
+    forced_induction::ForcedInduction,
+    fuel::{FuelConsumption, FuelTank},
+    idle_controller::IdleController,
+    ignition::{Ignition, StarterMotor},
+    rev_limiter::{RevLimiter, RevLimiterMode},
+    thermal::ThermalModel,
+};
+
 pub struct CombustionEngine {
-    /// Torque curve, specified as (rpm, torque (N))
-    pub torque_curve: Vec<(f32, f32)>,
-    pub idle_rpm: f32,
-    pub max_rpm: f32,
+    /// Torque curve, specified as (rpm, torque)
+    pub torque_curve: Vec<(Rpm, NewtonMetres)>,
+    /// Speed the idle controller holds the engine at without any throttle input
+    pub idle_rpm: Rpm,
+    /// Speed the rev limiter keeps the engine below
+    pub max_rpm: Rpm,
+    /// A running engine dragged below this speed stalls
+    pub stall_rpm: Rpm,
+    /// Speed at which a cranking engine catches and keeps running by itself, above stall_rpm
+    pub catch_rpm: Rpm,
+
+    pub starter: StarterMotor,
+    pub ignition: Ignition,
+    pub idle_controller: IdleController,
+    pub rev_limiter: RevLimiter,
+    /// Turbocharger or supercharger, None for a naturally aspirated engine
+    pub forced_induction: Option<ForcedInduction>,
+
+    /// Torque lost to pumping air against a closed throttle, specified as (rpm, torque).
+    /// It shrinks as the throttle opens, an empty curve disables engine braking
+    pub engine_brake_curve: Vec<(Rpm, NewtonMetres)>,
+    /// Above this speed the fuel is cut whenever the throttle is closed, so the engine only brakes
+    pub fuel_cut_rpm: Rpm,
+    /// Whether the fuel is currently cut, updated whenever update is called
+    pub fuel_cut: bool,
+
+    pub fuel_consumption: FuelConsumption,
+    pub fuel_tank: FuelTank,
+    /// Fuel burned during the last update (g/s)
+    pub fuel_flow: f32,
+
+    /// Coolant and oil temperatures, raising the friction while the oil is cold.
+    /// None leaves the engine at its operating temperature
+    pub thermal: Option<ThermalModel>,
 
-    pub current_rpm: f32,
+    pub current_rpm: Rpm,
 
     /// Engine inertia
     pub inertia: f32,
 
     /// Friction that is always applied, regardless of spinning speed
-    pub static_friction: f32,
-    /// Friction that goes up depending on RPM
+    pub static_friction: NewtonMetres,
+    /// Friction that goes up depending on RPM (Nm per rpm)
     pub variable_friction: f32,
     /// Multiplier for variable friction
     pub variable_friction_mult: f32,
 }
 
 impl CombustionEngine {
-    pub fn get_torque_data(&self) -> &Vec<(f32, f32)> {
+    /// Creates a running, naturally aspirated petrol engine idling at idle_rpm, with values typical of a road car
+    /// for everything else
+    pub fn new(torque_curve: Vec<(Rpm, NewtonMetres)>, idle_rpm: Rpm, max_rpm: Rpm, inertia: f32) -> Self {
+        Self {
+            torque_curve,
+            idle_rpm,
+            max_rpm,
+            // Stalls when dragged below 400 rpm
+            stall_rpm: Rpm(400.0),
+            // Catches once cranked up to 500 rpm
+            catch_rpm: Rpm(500.0),
+
+            // 100 Nm starter that cranks the engine up to 800 rpm
+            starter: StarterMotor {
+                torque: NewtonMetres(100.0),
+                max_rpm: Rpm(800.0),
+            },
+            ignition: Ignition::Running,
+            // Gentle PI controller, opening the throttle up to 15% to hold idle
+            idle_controller: IdleController::new(0.0005, 0.002, 0.0, 0.15),
+            // Fuel cut at max_rpm that restores the fuel 150 rpm below it, no launch control
+            rev_limiter: RevLimiter::new(RevLimiterMode::FuelCut { hysteresis: Rpm(150.0) }, None, MetresPerSec(1.0)),
+            // Naturally aspirated
+            forced_induction: None,
+
+            // No engine braking
+            engine_brake_curve: Vec::new(),
+            // Fuel cut on overrun down to 400 rpm above idle
+            fuel_cut_rpm: idle_rpm + Rpm(400.0),
+            fuel_cut: false,
+
+            // Petrol burned at 30% thermal efficiency
+            fuel_consumption: FuelConsumption::Efficiency {
+                thermal_efficiency: 0.3,
+                heating_value: 43.0,
+            },
+            // Full 60 l tank of petrol
+            fuel_tank: FuelTank {
+                capacity: 60.0,
+                fuel_density: 0.745,
+
+                fuel: 60.0,
+            },
+            fuel_flow: 0.0,
+
+            // Always at operating temperature
+            thermal: None,
+
+            current_rpm: idle_rpm,
+
+            inertia,
+            // 8 Nm plus 0.008 Nm per rpm of friction
+            static_friction: NewtonMetres(8.0),
+            variable_friction: 0.008,
+            variable_friction_mult: 1.0,
+        }
+    }
+
+    pub fn get_torque_data(&self) -> &Vec<(Rpm, NewtonMetres)> {
         &self.torque_curve
     }
 
-    fn find_closest_torque_points(&self, rpm: f32) -> ((f32, f32), (f32, f32)) {
-        for i in 0..(self.torque_curve.len()-1) {
-            let this = self.torque_curve[i];
-            let next = self.torque_curve[i+1];
-            if this.0 <= rpm && next.0 >= rpm {
-                return (this, next);
-            }
+    /// Current angular velocity of the crankshaft
+    pub fn angular_vel(&self) -> RadPerSec {
+        self.current_rpm.into()
+    }
+
+    /// Turns the key, cranking the engine until it catches. Does nothing if it is already running
+    pub fn start(&mut self) {
+        if !self.ignition.is_running() {
+            self.ignition = Ignition::Cranking;
         }
-        // If nothing was found, check if RPM exceeds the RPM limit or is below the minimum rpm
-        let min_rpm = self.torque_curve[0].0;
-        let max_rpm = self.torque_curve[self.torque_curve.len()-1].0;
-        if rpm < min_rpm {
-            return (self.torque_curve[0], self.torque_curve[0]);
+    }
+
+    /// Switches the ignition off, the engine stops producing torque and coasts down
+    pub fn stop(&mut self) {
+        self.ignition = Ignition::Off;
+    }
+
+    /// Naturally aspirated torque at full throttle
+    pub fn sample_torque_at_rpm(&self, rpm: Rpm) -> NewtonMetres {
+        sample_curve(&self.torque_curve, rpm)
+    }
+
+    /// Full throttle torque with the current boost
+    pub fn sample_boosted_torque_at_rpm(&self, rpm: Rpm) -> NewtonMetres {
+        let torque = self.sample_torque_at_rpm(rpm);
+        match &self.forced_induction {
+            Some(forced_induction) => torque * forced_induction.torque_multiplier(),
+            None => torque,
         }
-        if rpm > max_rpm {
-            return (self.torque_curve[self.torque_curve.len()-1], self.torque_curve[self.torque_curve.len()-1]);
+    }
+
+    /// Pumping losses at a closed throttle
+    pub fn sample_engine_brake_at_rpm(&self, rpm: Rpm) -> NewtonMetres {
+        if self.engine_brake_curve.is_empty() {
+            return NewtonMetres(0.0);
         }
-        unreachable!("We should not be here! Rpm: {}", rpm);
+        sample_curve(&self.engine_brake_curve, rpm)
     }
 
-    pub fn sample_torque_at_rpm(&self, rpm: f32) -> f32 {
-        let ((rpm_lower, torque_lower), (rpm_upper, torque_upper)) = self.find_closest_torque_points(rpm);
-        let t = (rpm - rpm_lower) / (rpm_upper - rpm_lower);
-        torque_lower + (torque_upper - torque_lower) * t
+    /// Returns the torque lost to mechanical friction, to pumping air and to driving a supercharger, in that order.
+    /// These are magnitudes, before fading out towards standstill
+    pub fn calc_losses(&self, throttle_input: f32) -> (NewtonMetres, NewtonMetres, NewtonMetres) {
+        let rpm = self.current_rpm.abs();
+        let friction_mult = self.thermal.as_ref().map_or(1.0, |thermal| thermal.friction_multiplier());
+        let friction = (self.static_friction + NewtonMetres(self.variable_friction * self.variable_friction_mult * rpm.0)) * friction_mult;
+        // The pistons pump air whether the engine is running or not, the throttle only changes how hard that is
+        let pumping = self.sample_engine_brake_at_rpm(rpm) * (1.0 - throttle_input);
+        let parasitic = self.forced_induction.as_ref().map_or(NewtonMetres(0.0), |forced_induction| forced_induction.parasitic_torque());
+        (friction, pumping, parasitic)
     }
 
-    pub fn calc_torque(&mut self, throttle_input: f32) -> (f32, f32) {
-        let torque = self.sample_torque_at_rpm(self.current_rpm) * throttle_input;
-        let friction = self.static_friction + (self.variable_friction * self.variable_friction_mult * self.current_rpm);
-        let friction_torque = friction.min(self.current_rpm * self.inertia * 2000f32);
+    /// Returns the torque driving the engine (combustion or the starter motor) and the torque lost to
+    /// friction, pumping and driving a supercharger
+    pub fn calc_torque(&mut self, throttle_input: f32) -> (NewtonMetres, NewtonMetres) {
+        let torque = match self.ignition {
+            Ignition::Running if !self.fuel_cut && !self.fuel_tank.is_empty() => {
+                let derate = self.thermal.as_ref().map_or(1.0, |thermal| thermal.torque_fraction());
+                self.sample_boosted_torque_at_rpm(self.current_rpm) * throttle_input * self.rev_limiter.torque_fraction * derate
+            }
+            Ignition::Cranking => self.starter.torque(self.current_rpm),
+            _ => NewtonMetres(0.0),
+        };
+        let (friction, pumping, parasitic) = self.calc_losses(throttle_input);
+        // Friction opposes the rotation
+        let friction_torque = (friction + pumping + parasitic) * self.current_rpm.signum();
 
         (torque, friction_torque)
     }
 
-    /// Updates the engine and returns the torque produced by the engine
-    pub fn update(&mut self, delta_s: f32, vehicle_speed: f32, throttle_input: f32, child: &mut crate::differential::Differential) {
-        let throttle_input = throttle_input.max(0.0).min(1.0);
+    /// Draws the fuel burned to produce the combustion torque from the tank
+    fn burn_fuel(&mut self, delta_s: f32, combustion_torque: NewtonMetres) {
+        let full_torque = self.sample_boosted_torque_at_rpm(self.current_rpm);
+        let load = if full_torque.0 > 0.0 { combustion_torque / full_torque } else { 0.0 };
+        self.fuel_flow = self.fuel_consumption.fuel_flow(self.current_rpm, load, combustion_torque * self.angular_vel());
+        self.fuel_tank.draw(self.fuel_flow * delta_s);
+    }
+
+    /// Updates the engine and the drivetrain connected to it
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, throttle_input: f32, child: &mut crate::clutch::Clutch) {
+        let throttle_input = throttle_input.clamp(0.0, 1.0);
+        // Lifting off at speed cuts the fuel until the engine drops back down, or the throttle opens again
+        self.fuel_cut = self.ignition.is_running() && throttle_input < CLOSED_THROTTLE && self.current_rpm > self.fuel_cut_rpm;
+        // The idle controller takes over whenever the driver asks for less throttle than it needs to hold idle
+        let idle_throttle = if self.ignition.is_running() && !self.fuel_cut {
+            self.idle_controller.update(delta_s, self.idle_rpm, self.current_rpm)
+        } else {
+            self.idle_controller.reset();
+            0.0
+        };
+        let throttle_input = throttle_input.max(idle_throttle);
+        self.rev_limiter.update(self.current_rpm, self.max_rpm, input.vehicle_speed);
+        if let Some(forced_induction) = &mut self.forced_induction {
+            forced_induction.update(delta_s, self.current_rpm, throttle_input);
+        }
 
         let (torque, friction_torque) = self.calc_torque(throttle_input);
-        let angular_vel = child.update(delta_s, vehicle_speed, torque - friction_torque);
+        // Friction can stop the engine within one update, but never turn it the other way
+        let stopping_torque = NewtonMetres(self.angular_vel().abs().0 * self.inertia / delta_s);
+        let friction_torque = friction_torque.clamp(-stopping_torque, stopping_torque);
+        // While cranking, the torque comes from the starter motor instead
+        let combustion_torque = if self.ignition.is_running() { torque } else { NewtonMetres(0.0) };
+        self.burn_fuel(delta_s, combustion_torque);
+        // Driving a supercharger heats the intake air rather than the engine, so only friction and pumping count here
+        let (friction, pumping, _) = self.calc_losses(throttle_input);
+        let combustion_power = combustion_torque * self.angular_vel();
+        let friction_power = friction * self.angular_vel().abs();
+        let pumping_power = pumping * self.angular_vel().abs();
+        if let Some(thermal) = &mut self.thermal {
+            thermal.update(delta_s, combustion_power, friction_power, pumping_power, input.vehicle_speed);
+        }
+        let clutch_torque = child.update(delta_s, input, self.angular_vel(), torque - friction_torque, self.inertia);
 
-        self.current_rpm = angular_vel.max(self.idle_rpm).min(self.max_rpm);
+        // The clutch torque is what the drivetrain pushes back on the engine with
+        let final_torque = torque - friction_torque - clutch_torque;
+        let angular_vel = self.angular_vel() + RadPerSec(final_torque.0 / self.inertia * delta_s);
+        self.current_rpm = Rpm::from(angular_vel);
+
+        match self.ignition {
+            Ignition::Cranking if self.current_rpm >= self.catch_rpm => self.ignition = Ignition::Running,
+            Ignition::Running if self.current_rpm < self.stall_rpm => self.ignition = Ignition::Stalled,
+            _ => {}
+        }
+    }
+}
+
+fn find_closest_points(curve: &[(Rpm, NewtonMetres)], rpm: Rpm) -> ((Rpm, NewtonMetres), (Rpm, NewtonMetres)) {
+    for i in 0..(curve.len()-1) {
+        let this = curve[i];
+        let next = curve[i+1];
+        if this.0 <= rpm && next.0 >= rpm {
+            return (this, next);
+        }
+    }
+    // If nothing was found, check if RPM exceeds the RPM limit or is below the minimum rpm
+    let min_rpm = curve[0].0;
+    let max_rpm = curve[curve.len()-1].0;
+    if rpm < min_rpm {
+        return (curve[0], curve[0]);
+    }
+    if rpm > max_rpm {
+        return (curve[curve.len()-1], curve[curve.len()-1]);
+    }
+    unreachable!("We should not be here! Rpm: {}", rpm.0);
+}
 
-        // let final_torque = torque - friction_torque - reaction_torque;
-        // let angular_accel = final_torque / self.inertia;
-        // self.current_rpm += angular_accel * delta_s;
-        // self.current_rpm = self.current_rpm.min(self.max_rpm).max(self.idle_rpm);
+/// Linearly interpolates a curve of (rpm, torque) points, holding the first and last point outside of it
+fn sample_curve(curve: &[(Rpm, NewtonMetres)], rpm: Rpm) -> NewtonMetres {
+    let ((rpm_lower, torque_lower), (rpm_upper, torque_upper)) = find_closest_points(curve, rpm);
+    // Outside of the curve both points are the same
+    if rpm_upper == rpm_lower {
+        return torque_lower;
     }
+    let t = (rpm - rpm_lower) / (rpm_upper - rpm_lower);
+    torque_lower + (torque_upper - torque_lower) * t
 }
@@ -0,0 +1,99 @@
+// Forced induction for a combustion engine, pushing more air into the cylinders than it could draw in by itself.
+// A turbocharger is driven by the exhaust, so its boost builds up with exhaust flow and lags behind the throttle.
+// A supercharger is driven by a belt off the crankshaft, so its boost follows engine speed directly,
+// but it costs some of the torque it adds to drive it.
+// Boost pressures are in bar above atmospheric pressure.
+
+use crate::units::{NewtonMetres, Rpm};
+
+use super::CLOSED_THROTTLE;
+
+/// Atmospheric pressure (bar)
+const ATMOSPHERIC_PRESSURE: f32 = 1.013;
+
+pub enum ForcedInduction {
+    Turbocharger(Turbocharger),
+    Supercharger(Supercharger),
+}
+
+impl ForcedInduction {
+    /// Current boost pressure (bar)
+    pub fn boost(&self) -> f32 {
+        match self {
+            Self::Turbocharger(turbo) => turbo.boost,
+            Self::Supercharger(supercharger) => supercharger.boost,
+        }
+    }
+
+    /// Multiplier on the naturally aspirated torque, for the extra air in the cylinders
+    pub fn torque_multiplier(&self) -> f32 {
+        let efficiency = match self {
+            Self::Turbocharger(turbo) => turbo.efficiency,
+            Self::Supercharger(supercharger) => supercharger.efficiency,
+        };
+        1.0 + self.boost() / ATMOSPHERIC_PRESSURE * efficiency
+    }
+
+    /// Torque it takes from the crankshaft to drive it
+    pub fn parasitic_torque(&self) -> NewtonMetres {
+        match self {
+            Self::Turbocharger(_) => NewtonMetres(0.0),
+            Self::Supercharger(supercharger) => supercharger.drive_torque * supercharger.boost,
+        }
+    }
+
+    pub fn update(&mut self, delta_s: f32, rpm: Rpm, throttle_input: f32) {
+        match self {
+            Self::Turbocharger(turbo) => turbo.update(delta_s, rpm, throttle_input),
+            Self::Supercharger(supercharger) => supercharger.update(rpm),
+        }
+    }
+}
+
+pub struct Turbocharger {
+    /// Boost pressure the wastegate opens at, the most the turbo will deliver (bar)
+    pub wastegate_boost: f32,
+    /// Exhaust flow, as rpm times throttle, needed to reach wastegate boost. Below it boost rises linearly
+    pub spool_rpm: Rpm,
+    /// Time constant of the boost building up (s)
+    pub spool_time: f32,
+    /// Time constant of the boost venting through the blow-off valve when the throttle closes (s)
+    pub blow_off_time: f32,
+    /// Fraction of the extra air that turns into torque, after losses like heating up the intake charge
+    pub efficiency: f32,
+
+    /// Current boost pressure (bar)
+    pub boost: f32,
+    /// Whether the blow-off valve is currently open
+    pub blow_off: bool,
+}
+
+impl Turbocharger {
+    pub fn update(&mut self, delta_s: f32, rpm: Rpm, throttle_input: f32) {
+        let exhaust_flow = rpm.abs() * throttle_input;
+        let target_boost = self.wastegate_boost * (exhaust_flow / self.spool_rpm).clamp(0.0, 1.0);
+
+        // Lifting off the throttle dumps the boost, rather than letting the turbo slowly run down
+        self.blow_off = throttle_input < CLOSED_THROTTLE;
+        let time_constant = if self.blow_off { self.blow_off_time } else { self.spool_time };
+        self.boost += (target_boost - self.boost) * (delta_s / time_constant).min(1.0);
+    }
+}
+
+pub struct Supercharger {
+    /// Boost pressure per rpm of engine speed (bar)
+    pub boost_per_rpm: f32,
+    /// Torque drawn from the crankshaft per bar of boost
+    pub drive_torque: NewtonMetres,
+    /// Fraction of the extra air that turns into torque, after losses like heating up the intake charge
+    pub efficiency: f32,
+
+    /// Current boost pressure (bar)
+    pub boost: f32,
+}
+
+impl Supercharger {
+    pub fn update(&mut self, rpm: Rpm) {
+        self.boost = self.boost_per_rpm * rpm.abs().0;
+    }
+}
@@ -0,0 +1,101 @@
+// Fuel consumption of a combustion engine, and the tank it draws from.
+// The fuel flow follows from the power the engine produces by combustion, either through a brake
+// specific fuel consumption (BSFC) map over engine speed and load, or a single thermal efficiency.
+// Because it is based on the torque burned rather than the torque delivered, an idling engine still
+// uses fuel to overcome its own friction. Once the tank runs dry, the engine stops producing torque.
+
+use crate::units::Rpm;
+
+pub enum FuelConsumption {
+    Map(BsfcMap),
+    /// Converts a fixed fraction of the energy in the fuel into work
+    Efficiency {
+        /// Fraction of the energy in the fuel turned into work (0-1)
+        thermal_efficiency: f32,
+        /// Energy in the fuel (MJ/kg), around 43 for petrol
+        heating_value: f32,
+    },
+}
+
+impl FuelConsumption {
+    /// Returns the fuel flow (g/s) needed to produce power (W) at an engine speed and load (0-1)
+    pub fn fuel_flow(&self, rpm: Rpm, load: f32, power: f32) -> f32 {
+        let power = power.max(0.0);
+        match self {
+            Self::Map(map) => map.sample(rpm, load) * power / 1000.0 / 3600.0,
+            Self::Efficiency { thermal_efficiency, heating_value } => power / (thermal_efficiency * heating_value * 1000.0),
+        }
+    }
+}
+
+/// Brake specific fuel consumption (g/kWh) over engine speed and load.
+/// It is applied to the power produced by combustion, before the friction and pumping losses are taken off
+pub struct BsfcMap {
+    /// Engine speeds the map is specified at, in ascending order
+    pub rpm_points: Vec<Rpm>,
+    /// Loads the map is specified at as a fraction of full throttle torque (0-1), in ascending order
+    pub load_points: Vec<f32>,
+    /// Fuel consumption (g/kWh), indexed as [rpm][load]
+    pub bsfc: Vec<Vec<f32>>,
+}
+
+impl BsfcMap {
+    /// Bilinearly interpolates the map, holding the outermost values outside of it.
+    /// An empty map returns 0
+    pub fn sample(&self, rpm: Rpm, load: f32) -> f32 {
+        let (Some((rpm_lower, rpm_upper, rpm_t)), Some((load_lower, load_upper, load_t))) = (
+            find_interpolation_points(&self.rpm_points, rpm.0, |rpm| rpm.0),
+            find_interpolation_points(&self.load_points, load, |load| *load),
+        ) else {
+            return 0.0;
+        };
+
+        let sample_row = |row: &Vec<f32>| row[load_lower] + (row[load_upper] - row[load_lower]) * load_t;
+        let lower = sample_row(&self.bsfc[rpm_lower]);
+        let upper = sample_row(&self.bsfc[rpm_upper]);
+        lower + (upper - lower) * rpm_t
+    }
+}
+
+/// Returns the indices of the points around x, and how far x is between them (0-1), or None without any points.
+/// value gives the position of a point
+fn find_interpolation_points<T>(points: &[T], x: f32, value: fn(&T) -> f32) -> Option<(usize, usize, f32)> {
+    let last = points.len().checked_sub(1)?;
+    if x <= value(&points[0]) {
+        return Some((0, 0, 0.0));
+    }
+    if x >= value(&points[last]) {
+        return Some((last, last, 0.0));
+    }
+    let upper = points.iter().position(|point| value(point) >= x).unwrap_or(last);
+    let lower = upper - 1;
+    let (lower_x, upper_x) = (value(&points[lower]), value(&points[upper]));
+    Some((lower, upper, (x - lower_x) / (upper_x - lower_x)))
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FuelTank {
+    /// Most fuel the tank can hold (l)
+    pub capacity: f32,
+    /// Density of the fuel (kg/l), around 0.745 for petrol
+    pub fuel_density: f32,
+
+    /// Fuel currently in the tank (l)
+    pub fuel: f32,
+}
+
+impl FuelTank {
+    /// Mass of the fuel in the tank (kg)
+    pub fn mass(&self) -> f32 {
+        self.fuel * self.fuel_density
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fuel <= 0.0
+    }
+
+    /// Takes fuel (g) out of the tank, as far as there is any left
+    pub fn draw(&mut self, fuel_mass: f32) {
+        self.fuel = (self.fuel - fuel_mass / 1000.0 / self.fuel_density).clamp(0.0, self.capacity);
+    }
+}
@@ -0,0 +1,66 @@
+// A closed loop idle speed controller, like the idle air control of a real engine.
+// It opens the throttle a little whenever the engine drops below its idle speed, so the idle
+// droops when a load is put on the engine and then recovers, rather than being held rigidly.
+
+use crate::units::Rpm;
+
+#[derive(Debug, Copy, Clone)]
+pub struct IdleController {
+    /// Throttle per rpm below idle
+    pub proportional_gain: f32,
+    /// Throttle per rpm below idle, per second it has been there
+    pub integral_gain: f32,
+    /// Throttle per rpm/s the engine is dropping towards idle at
+    pub derivative_gain: f32,
+    /// Most throttle the controller will ever open (0-1)
+    pub max_throttle: f32,
+
+    /// Accumulated speed error (rpm*s)
+    pub integral: f32,
+    /// Speed error during the last update (rpm), None right after a reset
+    pub last_error: Option<f32>,
+    /// Throttle requested during the last update (0-1)
+    pub throttle: f32,
+}
+
+impl IdleController {
+    pub fn new(proportional_gain: f32, integral_gain: f32, derivative_gain: f32, max_throttle: f32) -> Self {
+        Self {
+            proportional_gain,
+            integral_gain,
+            derivative_gain,
+            max_throttle,
+
+            integral: 0.0,
+            last_error: None,
+            throttle: 0.0,
+        }
+    }
+
+    /// Forgets everything the controller has built up, for when the engine stops
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_error = None;
+        self.throttle = 0.0;
+    }
+
+    /// Returns the throttle needed to hold the engine at idle_rpm (0 to max_throttle)
+    pub fn update(&mut self, delta_s: f32, idle_rpm: Rpm, current_rpm: Rpm) -> f32 {
+        let error = (idle_rpm - current_rpm).0;
+        // Without an earlier error to compare against, the first update after a reset has no derivative,
+        // rather than kicking the throttle open from a stale error
+        let derivative = self.last_error.map_or(0.0, |last_error| (error - last_error) / delta_s);
+        self.last_error = Some(error);
+
+        // Only integrate as far as the throttle can follow, so the controller does not wind up
+        // while the engine is revved well above idle or bogged down under a load it cannot hold
+        self.integral += error * delta_s;
+        if self.integral_gain > 0.0 {
+            self.integral = self.integral.clamp(0.0, self.max_throttle / self.integral_gain);
+        }
+
+        let throttle = self.proportional_gain * error + self.integral_gain * self.integral + self.derivative_gain * derivative;
+        self.throttle = throttle.clamp(0.0, self.max_throttle);
+        self.throttle
+    }
+}
@@ -0,0 +1,40 @@
+// The ignition and starter motor of an engine.
+// Turning the key cranks the engine with the starter motor, until it spins fast enough to catch
+// and keep itself running. A running engine that gets dragged below its stall speed dies.
+
+use crate::units::{NewtonMetres, Rpm};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ignition {
+    /// Switched off, the engine only coasts down
+    Off,
+    /// The starter motor is turning the engine over
+    Cranking,
+    /// Running by itself, producing torque
+    Running,
+    /// Died after dropping below its stall speed, it has to be started again
+    Stalled,
+}
+
+impl Ignition {
+    /// Whether the engine is currently burning fuel
+    pub fn is_running(&self) -> bool {
+        *self == Self::Running
+    }
+}
+
+/// An electric motor that turns the engine over while cranking
+#[derive(Debug, Copy, Clone)]
+pub struct StarterMotor {
+    /// Torque at the crankshaft with the engine standing still
+    pub torque: NewtonMetres,
+    /// Engine speed at which the starter can no longer drive it, torque falls off linearly towards this
+    pub max_rpm: Rpm,
+}
+
+impl StarterMotor {
+    /// Returns the torque on the crankshaft at the current engine speed
+    pub fn torque(&self, rpm: Rpm) -> NewtonMetres {
+        self.torque * (1.0 - rpm / self.max_rpm).clamp(0.0, 1.0)
+    }
+}
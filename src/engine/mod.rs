@@ -1,4 +1,13 @@
 pub mod combustion_engine;
+pub mod ignition;
+pub mod idle_controller;
+pub mod rev_limiter;
+pub mod forced_induction;
+pub mod fuel;
+pub mod thermal;
+
+/// Throttle input below which the driver is considered to be off the throttle
+const CLOSED_THROTTLE: f32 = 0.01;
 
 pub enum Engine {
     CombustionEngine(combustion_engine::CombustionEngine),
@@ -6,13 +15,29 @@ pub enum Engine {
 
 pub struct EngineContainer {
     pub engine: Engine,
-    pub child: crate::differential::Differential,
+    pub child: crate::clutch::Clutch,
 }
 
 impl EngineContainer {
-    pub fn update(&mut self, delta_s: f32, vehicle_speed: f32, throttle_input: f32) {
+    /// All wheels driven by the engine
+    pub fn wheels(&self) -> Vec<&crate::wheels::Wheel> {
+        self.child.child.child().wheels()
+    }
+
+    pub fn wheels_mut(&mut self) -> Vec<&mut crate::wheels::Wheel> {
+        self.child.child.child_mut().wheels_mut()
+    }
+
+    /// Mass of the fuel carried for the engine (kg)
+    pub fn fuel_mass(&self) -> f32 {
+        match &self.engine {
+            Engine::CombustionEngine(engine) => engine.fuel_tank.mass(),
+        }
+    }
+
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, throttle_input: f32) {
         match &mut self.engine {
-            Engine::CombustionEngine(engine) => engine.update(delta_s, vehicle_speed, throttle_input, &mut self.child),
+            Engine::CombustionEngine(engine) => engine.update(delta_s, input, throttle_input, &mut self.child),
         }
     }
 }
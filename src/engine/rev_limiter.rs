@@ -0,0 +1,77 @@
+// The rev limiter of an engine.
+// Rather than holding the engine at its maximum speed, it cuts combustion whenever the engine
+// reaches it, and lets it burn again once it has dropped back down. This makes the engine speed
+// bounce off the limit, like it does on a real engine. While the vehicle stands still, launch
+// control can hold the engine at a lower target instead, ready to pull away.
+
+use crate::units::{MetresPerSec, Rpm};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RevLimiterMode {
+    /// Cuts all combustion torque as soon as the engine reaches the limit
+    HardCut,
+    /// Retards the ignition, fading combustion torque out linearly over this range below the limit
+    SoftCut { range: Rpm },
+    /// Cuts the fuel at the limit, and only restores it once the engine has dropped this far below it
+    FuelCut { hysteresis: Rpm },
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RevLimiter {
+    pub mode: RevLimiterMode,
+    /// Engine speed launch control holds the engine at while standing still, None disables it
+    pub launch_rpm: Option<Rpm>,
+    /// Vehicle speed below which launch control is active
+    pub launch_speed: MetresPerSec,
+
+    /// Whether the limiter is currently cutting torque
+    pub cutting: bool,
+    /// Fraction of the combustion torque let through during the last update (0-1)
+    pub torque_fraction: f32,
+}
+
+impl RevLimiter {
+    pub fn new(mode: RevLimiterMode, launch_rpm: Option<Rpm>, launch_speed: MetresPerSec) -> Self {
+        Self {
+            mode,
+            launch_rpm,
+            launch_speed,
+
+            cutting: false,
+            torque_fraction: 1.0,
+        }
+    }
+
+    /// Returns the engine speed the limiter is currently holding the engine below
+    pub fn target_rpm(&self, max_rpm: Rpm, vehicle_speed: MetresPerSec) -> Rpm {
+        match self.launch_rpm {
+            Some(launch_rpm) if vehicle_speed.abs() < self.launch_speed => launch_rpm.min(max_rpm),
+            _ => max_rpm,
+        }
+    }
+
+    /// Returns the fraction of the combustion torque the engine is allowed to produce (0-1)
+    pub fn update(&mut self, current_rpm: Rpm, max_rpm: Rpm, vehicle_speed: MetresPerSec) -> f32 {
+        let target_rpm = self.target_rpm(max_rpm, vehicle_speed);
+        self.torque_fraction = match self.mode {
+            RevLimiterMode::HardCut => {
+                self.cutting = current_rpm >= target_rpm;
+                if self.cutting { 0.0 } else { 1.0 }
+            }
+            RevLimiterMode::SoftCut { range } => {
+                let fraction = ((target_rpm - current_rpm) / range).clamp(0.0, 1.0);
+                self.cutting = fraction < 1.0;
+                fraction
+            }
+            RevLimiterMode::FuelCut { hysteresis } => {
+                if current_rpm >= target_rpm {
+                    self.cutting = true;
+                } else if current_rpm < target_rpm - hysteresis {
+                    self.cutting = false;
+                }
+                if self.cutting { 0.0 } else { 1.0 }
+            }
+        };
+        self.torque_fraction
+    }
+}
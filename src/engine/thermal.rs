@@ -0,0 +1,118 @@
+// A lumped thermal model of a combustion engine, with the coolant (and engine block) and the oil as two masses.
+// Combustion heats the coolant, with the part of the fuel's energy that is not turned into work or blown out
+// of the exhaust, and so does the work of pumping air through the engine. Only friction heats the oil.
+// The oil passes its heat on to the coolant, which loses it through the radiator once the thermostat opens,
+// and a little straight to the air around the engine.
+// Cold oil is thick, raising the friction of the engine until it has warmed up.
+// Temperatures are in degrees Celsius.
+
+use crate::units::MetresPerSec;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Radiator {
+    /// Heat transfer to the air while standing still, with the fan running (W/K)
+    pub base_conductance: f32,
+    /// Extra heat transfer per m/s of vehicle speed, from the air rammed through it (W/K)
+    pub conductance_per_speed: f32,
+}
+
+impl Radiator {
+    /// Heat transfer to the air at a vehicle speed (W/K)
+    pub fn conductance(&self, vehicle_speed: MetresPerSec) -> f32 {
+        self.base_conductance + self.conductance_per_speed * vehicle_speed.0.abs()
+    }
+}
+
+/// Keeps the coolant from flowing through the radiator until the engine has warmed up
+#[derive(Debug, Copy, Clone)]
+pub struct Thermostat {
+    /// Coolant temperature at which the thermostat starts to open
+    pub opening_temp: f32,
+    /// Coolant temperature at which the thermostat is fully open
+    pub fully_open_temp: f32,
+}
+
+impl Thermostat {
+    /// How far the thermostat is open at a coolant temperature (0-1)
+    pub fn opening(&self, coolant_temp: f32) -> f32 {
+        ((coolant_temp - self.opening_temp) / (self.fully_open_temp - self.opening_temp)).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ThermalModel {
+    /// Heat capacity of the coolant and engine block (J/K)
+    pub coolant_heat_capacity: f32,
+    /// Heat capacity of the oil (J/K)
+    pub oil_heat_capacity: f32,
+    /// Heat rejected into the coolant per watt of combustion power
+    pub combustion_heat_ratio: f32,
+    /// Heat transfer between the oil and the coolant (W/K)
+    pub oil_conductance: f32,
+    /// Heat transfer from the engine block straight to the air around it (W/K)
+    pub passive_conductance: f32,
+    pub radiator: Radiator,
+    pub thermostat: Thermostat,
+    /// Temperature of the air around the engine
+    pub ambient_temp: f32,
+
+    /// Friction multiplier with the oil at cold_oil_temp, falling linearly to 1 at warm_oil_temp
+    pub cold_friction_mult: f32,
+    pub cold_oil_temp: f32,
+    pub warm_oil_temp: f32,
+
+    /// Coolant temperatures between which torque is derated to protect the engine, from full torque down to none.
+    /// None disables derating
+    pub derate_range: Option<(f32, f32)>,
+    /// Coolant temperature at which the engine is damaged and stops producing torque for good. None disables damage
+    pub damage_temp: Option<f32>,
+
+    pub coolant_temp: f32,
+    pub oil_temp: f32,
+    /// Whether the engine has been damaged by overheating
+    pub damaged: bool,
+}
+
+impl ThermalModel {
+    /// Multiplier on the engine friction at the current oil temperature
+    pub fn friction_multiplier(&self) -> f32 {
+        // Without a range to warm up over, the engine is always warm
+        let warmth = if self.warm_oil_temp > self.cold_oil_temp {
+            ((self.oil_temp - self.cold_oil_temp) / (self.warm_oil_temp - self.cold_oil_temp)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.cold_friction_mult + (1.0 - self.cold_friction_mult) * warmth
+    }
+
+    /// Fraction of the combustion torque the engine is allowed to produce at the current coolant temperature (0-1)
+    pub fn torque_fraction(&self) -> f32 {
+        if self.damaged {
+            return 0.0;
+        }
+        match self.derate_range {
+            Some((start_temp, end_temp)) if end_temp > start_temp => 1.0 - ((self.coolant_temp - start_temp) / (end_temp - start_temp)).clamp(0.0, 1.0),
+            // Without a range to derate over, the torque is cut off at once
+            Some((_, end_temp)) => if self.coolant_temp < end_temp { 1.0 } else { 0.0 },
+            None => 1.0,
+        }
+    }
+
+    /// Heats the engine with combustion, friction and pumping power (W), and cools it down to the air
+    pub fn update(&mut self, delta_s: f32, combustion_power: f32, friction_power: f32, pumping_power: f32, vehicle_speed: MetresPerSec) {
+        let combustion_heat = combustion_power.max(0.0) * self.combustion_heat_ratio + pumping_power.abs();
+        let friction_heat = friction_power.abs();
+        let oil_to_coolant = self.oil_conductance * (self.oil_temp - self.coolant_temp);
+        let radiator_conductance = self.radiator.conductance(vehicle_speed) * self.thermostat.opening(self.coolant_temp);
+        let coolant_to_air = (radiator_conductance + self.passive_conductance) * (self.coolant_temp - self.ambient_temp);
+
+        self.coolant_temp += (combustion_heat + oil_to_coolant - coolant_to_air) / self.coolant_heat_capacity * delta_s;
+        self.oil_temp += (friction_heat - oil_to_coolant) / self.oil_heat_capacity * delta_s;
+
+        if let Some(damage_temp) = self.damage_temp {
+            if self.coolant_temp >= damage_temp {
+                self.damaged = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// Ground materials, describing how a surface affects the tyres driving over it.
+// Friction values are multipliers on the tyre friction coefficient, so dry asphalt is 1.0.
+
+use std::collections::HashMap;
+
+use crate::units::{MetresPerSec, Newtons};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GroundMaterial {
+    /// Multiplier on the tyre friction coefficient while the tyre is gripping
+    pub static_friction: f32,
+    /// Multiplier on the tyre friction coefficient while the tyre is sliding
+    pub sliding_friction: f32,
+    /// Sliding speed at which the sliding friction is fully reached
+    pub sliding_velocity: MetresPerSec,
+    /// Rolling resistance coefficient caused by the surface, on top of that of the tyre itself
+    pub rolling_resistance: f32,
+    /// Load needed to push the tyre one metre into the ground (N/m), infinite for hard surfaces
+    pub deformation_stiffness: f32,
+    /// Maximum depth the tyre can sink into the ground (m)
+    pub max_sinkage: f32,
+}
+
+impl GroundMaterial {
+    pub const ASPHALT: Self = Self {
+        static_friction: 1.0,
+        sliding_friction: 1.0,
+        sliding_velocity: MetresPerSec(1.0),
+        rolling_resistance: 0.0,
+        deformation_stiffness: f32::INFINITY,
+        max_sinkage: 0.0,
+    };
+
+    pub const WET_ASPHALT: Self = Self {
+        static_friction: 0.7,
+        sliding_friction: 0.55,
+        sliding_velocity: MetresPerSec(1.0),
+        rolling_resistance: 0.002,
+        deformation_stiffness: f32::INFINITY,
+        max_sinkage: 0.0,
+    };
+
+    pub const GRAVEL: Self = Self {
+        static_friction: 0.65,
+        sliding_friction: 0.6,
+        sliding_velocity: MetresPerSec(2.0),
+        rolling_resistance: 0.02,
+        deformation_stiffness: 2_000_000.0,
+        max_sinkage: 0.02,
+    };
+
+    pub const MUD: Self = Self {
+        static_friction: 0.4,
+        sliding_friction: 0.3,
+        sliding_velocity: MetresPerSec(2.0),
+        rolling_resistance: 0.08,
+        deformation_stiffness: 200_000.0,
+        max_sinkage: 0.15,
+    };
+
+    pub const ICE: Self = Self {
+        static_friction: 0.12,
+        sliding_friction: 0.08,
+        sliding_velocity: MetresPerSec(0.5),
+        rolling_resistance: 0.0,
+        deformation_stiffness: f32::INFINITY,
+        max_sinkage: 0.0,
+    };
+
+    pub const SNOW: Self = Self {
+        static_friction: 0.35,
+        sliding_friction: 0.3,
+        sliding_velocity: MetresPerSec(1.0),
+        rolling_resistance: 0.03,
+        deformation_stiffness: 400_000.0,
+        max_sinkage: 0.1,
+    };
+
+    pub const GRASS: Self = Self {
+        static_friction: 0.55,
+        sliding_friction: 0.4,
+        sliding_velocity: MetresPerSec(1.0),
+        rolling_resistance: 0.03,
+        deformation_stiffness: 1_000_000.0,
+        max_sinkage: 0.03,
+    };
+
+    pub fn friction_coeff(&self, sliding_vel: MetresPerSec) -> f32 {
+        let t = (sliding_vel / self.sliding_velocity).min(1.0);
+        self.static_friction + (self.sliding_friction - self.static_friction) * t
+    }
+
+    /// Returns how deep the tyre sinks into the ground (m)
+    pub fn sinkage(&self, load: Newtons) -> f32 {
+        (load.0.max(0.0) / self.deformation_stiffness).min(self.max_sinkage)
+    }
+}
+
+/// A collection of ground materials, looked up by name
+pub struct GroundRegistry {
+    materials: HashMap<String, GroundMaterial>,
+}
+
+impl Default for GroundRegistry {
+    /// A registry containing all built in materials
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("asphalt", GroundMaterial::ASPHALT);
+        registry.register("wet_asphalt", GroundMaterial::WET_ASPHALT);
+        registry.register("gravel", GroundMaterial::GRAVEL);
+        registry.register("mud", GroundMaterial::MUD);
+        registry.register("ice", GroundMaterial::ICE);
+        registry.register("snow", GroundMaterial::SNOW);
+        registry.register("grass", GroundMaterial::GRASS);
+        registry
+    }
+}
+
+impl GroundRegistry {
+    pub fn empty() -> Self {
+        Self {
+            materials: HashMap::new(),
+        }
+    }
+
+    /// Adds a material, replacing any existing material with the same name
+    pub fn register(&mut self, name: &str, material: GroundMaterial) {
+        self.materials.insert(name.to_string(), material);
+    }
+
+    pub fn get(&self, name: &str) -> Option<GroundMaterial> {
+        self.materials.get(name).copied()
+    }
+}
@@ -1,3 +1,12 @@
 pub mod engine;
+pub mod clutch;
+pub mod transmission;
 pub mod differential;
 pub mod wheels;
+pub mod suspension;
+pub mod ground;
+pub mod vehicle;
+pub mod units;
+
+/// Gravitational acceleration (m/s^2)
+pub const GRAVITY: f32 = 9.81;
@@ -0,0 +1,159 @@
+// A quarter car suspension model for a single corner.
+// The corner of the body sits on a spring and damper, which sit on the wheel,
+// which in turn sits on the road through the vertical stiffness of the tyre.
+// The spring is preloaded so the corner sits at ride height under its static load,
+// like a coilover with its spring perch set up for the car.
+
+use crate::{
+    units::{MetresPerSec, Newtons},
+    GRAVITY,
+};
+
+/// The tyre and suspension are too stiff to integrate at a regular update rate,
+/// so every update is split up into substeps no longer than this (s)
+const MAX_SUBSTEP: f32 = 0.002;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Suspension {
+    /// Spring rate (N/m)
+    pub spring_rate: f32,
+    /// Damping while compressing (N per m/s)
+    pub bump_damping: f32,
+    /// Damping while extending (N per m/s)
+    pub rebound_damping: f32,
+    /// Travel from ride height until the bump stop is hit (m)
+    pub bump_travel: f32,
+    /// Travel from ride height until the suspension is fully extended (m)
+    pub droop_travel: f32,
+    /// Spring rate of the bump stop and droop limiter (N/m)
+    pub bump_stop_rate: f32,
+    /// Height of the body above the ground at rest (m)
+    pub ride_height: f32,
+    /// Vertical stiffness of the tyre (N/m)
+    pub tyre_stiffness: f32,
+
+    /// Height of the road below the wheel relative to flat ground (m), set this to drive over bumps
+    pub road_height: f32,
+
+    /// Height of the body corner relative to its rest position (m)
+    pub body_height: f32,
+    /// Vertical velocity of the body corner
+    pub body_vel: MetresPerSec,
+    /// Height of the wheel relative to its rest position (m)
+    pub wheel_height: f32,
+    /// Vertical velocity of the wheel
+    pub wheel_vel: MetresPerSec,
+    /// Force between the tyre and the road, updated whenever update is called
+    pub normal_force: Newtons,
+    /// Force pushing the body and wheel apart, including the anti-roll bars, updated whenever update is called
+    pub force: Newtons,
+}
+
+impl Suspension {
+    /// Compression relative to ride height (m), positive when compressed
+    pub fn travel(&self) -> f32 {
+        self.wheel_height - self.body_height
+    }
+
+    /// Compression speed, positive when compressing
+    pub fn travel_vel(&self) -> MetresPerSec {
+        self.wheel_vel - self.body_vel
+    }
+
+    /// Current height of the body above the road (m)
+    pub fn current_ride_height(&self) -> f32 {
+        self.ride_height + self.body_height - self.road_height
+    }
+
+    /// Force of the spring, damper and bump stops, pushing the body and wheel apart.
+    /// static_load is the load the spring carries at ride height
+    pub fn suspension_force(&self, static_load: Newtons) -> Newtons {
+        let travel = self.travel();
+        let travel_vel = self.travel_vel().0;
+
+        // A spring can push, but never pull
+        let spring_force = (static_load.0 + self.spring_rate * travel).max(0.0);
+        let bump_stop_force = if travel > self.bump_travel {
+            self.bump_stop_rate * (travel - self.bump_travel)
+        } else if travel < -self.droop_travel {
+            // Fully extended, the wheel now hangs from the body
+            -self.bump_stop_rate * (-self.droop_travel - travel)
+        } else {
+            0.0
+        };
+        let damping = if travel_vel > 0.0 { self.bump_damping } else { self.rebound_damping };
+
+        Newtons(spring_force + bump_stop_force + damping * travel_vel)
+    }
+
+    /// Returns the force between the tyre and the road.
+    /// body_load:      load the body puts on this corner, including weight transfer
+    /// sprung_mass:    mass of the body resting on this corner at rest (kg)
+    /// unsprung_mass:  mass of the wheel and everything moving with it (kg)
+    /// anti_roll_force: extra force from anti-roll bars, pushing the body and wheel apart
+    pub fn update(&mut self, delta_s: f32, body_load: Newtons, sprung_mass: f32, unsprung_mass: f32, anti_roll_force: Newtons) -> Newtons {
+        let substeps = (delta_s / MAX_SUBSTEP).ceil().max(1.0);
+        let dt = delta_s / substeps;
+        for _ in 0..(substeps as usize) {
+            self.step_wheel(dt, sprung_mass, unsprung_mass, anti_roll_force);
+
+            self.body_vel += MetresPerSec((self.force - body_load).0 / sprung_mass * dt);
+            self.body_height += self.body_vel.0 * dt;
+        }
+
+        self.normal_force
+    }
+
+    /// Same as update, but for a body that moves by itself, like a rigid body with its own pitch and roll.
+    /// Only the wheel is integrated here, the body should push back on the corner with force.
+    /// body_height:    height of the body corner relative to its rest position (m)
+    /// body_vel:       vertical velocity of the body corner
+    pub fn update_mounted(&mut self, delta_s: f32, body_height: f32, body_vel: MetresPerSec, sprung_mass: f32, unsprung_mass: f32, anti_roll_force: Newtons) -> Newtons {
+        self.body_vel = body_vel;
+
+        let substeps = (delta_s / MAX_SUBSTEP).ceil().max(1.0);
+        let dt = delta_s / substeps;
+        // Move the body corner along linearly, from wherever it was at the last update
+        let body_height_step = (body_height - self.body_height) / substeps;
+        for _ in 0..(substeps as usize) {
+            self.body_height += body_height_step;
+            self.step_wheel(dt, sprung_mass, unsprung_mass, anti_roll_force);
+        }
+
+        self.normal_force
+    }
+
+    /// Integrates the wheel over a single substep, updating force and normal_force
+    fn step_wheel(&mut self, dt: f32, sprung_mass: f32, unsprung_mass: f32, anti_roll_force: Newtons) {
+        let static_load = Newtons(sprung_mass * GRAVITY);
+        let unsprung_weight = Newtons(unsprung_mass * GRAVITY);
+
+        self.force = self.suspension_force(static_load) + anti_roll_force;
+        // The tyre can only push against the road, the wheel is airborne otherwise
+        let tyre_force = Newtons(self.tyre_stiffness * (self.road_height - self.wheel_height));
+        self.normal_force = (static_load + unsprung_weight + tyre_force).max(Newtons(0.0));
+
+        self.wheel_vel += MetresPerSec((self.normal_force - self.force - unsprung_weight).0 / unsprung_mass * dt);
+        self.wheel_height += self.wheel_vel.0 * dt;
+    }
+}
+
+/// An anti-roll bar, linking the suspension of a left and right wheel.
+/// It resists the two sides compressing by different amounts.
+#[derive(Debug, Copy, Clone)]
+pub struct AntiRollBar {
+    /// Force per metre of difference in travel between both sides (N/m)
+    pub stiffness: f32,
+    /// Index of the left wheel
+    pub left: usize,
+    /// Index of the right wheel
+    pub right: usize,
+}
+
+impl AntiRollBar {
+    /// Returns the force on the left and right suspension
+    pub fn forces(&self, left_travel: f32, right_travel: f32) -> (Newtons, Newtons) {
+        let force = Newtons(self.stiffness * (left_travel - right_travel));
+        (force, -force)
+    }
+}
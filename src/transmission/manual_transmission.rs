@@ -0,0 +1,99 @@
+use crate::units::{NewtonMetres, RadPerSec};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Gear {
+    Reverse,
+    Neutral,
+    /// Forward gear, where 1 is first gear
+    Forward(usize),
+}
+
+/// A manual H-pattern gearbox, including the final drive
+pub struct ManualTransmission {
+    /// Forward gear ratios, starting at first gear
+    pub gear_ratios: Vec<f32>,
+    /// Efficiency of each forward gear (0-1), same order as gear_ratios
+    pub gear_efficiencies: Vec<f32>,
+    /// Reverse gear ratio, specified as a positive number
+    pub reverse_ratio: f32,
+    /// Efficiency of the reverse gear (0-1)
+    pub reverse_efficiency: f32,
+    /// Final drive ratio, applied on top of the selected gear
+    pub final_drive: f32,
+
+    pub gear: Gear,
+
+    pub child: crate::differential::Differential,
+}
+
+impl ManualTransmission {
+    /// Selects a gear. Returns false if the gear does not exist, in which case nothing changes
+    pub fn set_gear(&mut self, gear: Gear) -> bool {
+        if let Gear::Forward(n) = gear {
+            if n == 0 || n > self.gear_ratios.len() { return false; }
+        }
+        self.gear = gear;
+        true
+    }
+
+    pub fn shift_up(&mut self) -> bool {
+        match self.gear {
+            Gear::Reverse => self.set_gear(Gear::Neutral),
+            Gear::Neutral => self.set_gear(Gear::Forward(1)),
+            Gear::Forward(n) => self.set_gear(Gear::Forward(n + 1)),
+        }
+    }
+
+    pub fn shift_down(&mut self) -> bool {
+        match self.gear {
+            Gear::Reverse => false,
+            Gear::Neutral => self.set_gear(Gear::Reverse),
+            Gear::Forward(1) => self.set_gear(Gear::Neutral),
+            Gear::Forward(n) => self.set_gear(Gear::Forward(n - 1)),
+        }
+    }
+
+    /// Total ratio between the input shaft and the output, including the final drive.
+    /// Negative in reverse, zero in neutral or in a forward gear that does not exist
+    pub fn current_ratio(&self) -> f32 {
+        match self.gear {
+            Gear::Reverse => -self.reverse_ratio * self.final_drive,
+            Gear::Neutral => 0.0,
+            Gear::Forward(n) => n.checked_sub(1).and_then(|i| self.gear_ratios.get(i)).map_or(0.0, |ratio| ratio * self.final_drive),
+        }
+    }
+
+    pub fn current_efficiency(&self) -> f32 {
+        match self.gear {
+            Gear::Reverse => self.reverse_efficiency,
+            Gear::Neutral => 1.0,
+            Gear::Forward(n) => n.checked_sub(1).and_then(|i| self.gear_efficiencies.get(i)).copied().unwrap_or(1.0),
+        }
+    }
+
+    /// Angular velocity of the input shaft in the currently selected gear, from how fast the output turns
+    pub fn input_angular_vel(&self) -> RadPerSec {
+        self.child.angular_vel() * self.current_ratio()
+    }
+
+    /// Inertia of everything behind the transmission, as felt at the input shaft, zero in neutral
+    pub fn effective_inertia(&self) -> f32 {
+        let ratio = self.current_ratio();
+        if ratio == 0.0 {
+            return 0.0;
+        }
+        self.child.effective_inertia() / (ratio * ratio)
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        let ratio = self.current_ratio();
+        let torque_out = torque_in * ratio * self.current_efficiency();
+
+        let angular_vel = self.child.update(delta_s, input, torque_out);
+
+        // In neutral the ratio is zero, so the input shaft reads as standing still. The clutch does not
+        // drive against it then, and lets the engine spin freely
+        angular_vel * ratio
+    }
+}
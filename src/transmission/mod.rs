@@ -0,0 +1,55 @@
+pub mod manual_transmission;
+
+pub enum Transmission {
+    Manual(manual_transmission::ManualTransmission),
+}
+
+impl Transmission {
+    /// Whether the input shaft is disconnected from the output, in neutral or in a gear that does not exist
+    pub fn in_neutral(&self) -> bool {
+        match self {
+            Self::Manual(transmission) => transmission.current_ratio() == 0.0,
+        }
+    }
+
+    /// The differential (or wheel) driven by the transmission
+    pub fn child(&self) -> &crate::differential::Differential {
+        match self {
+            Self::Manual(transmission) => &transmission.child,
+        }
+    }
+
+    pub fn child_mut(&mut self) -> &mut crate::differential::Differential {
+        match self {
+            Self::Manual(transmission) => &mut transmission.child,
+        }
+    }
+
+    /// Total ratio between the input shaft and the output, zero in neutral
+    pub fn current_ratio(&self) -> f32 {
+        match self {
+            Self::Manual(transmission) => transmission.current_ratio(),
+        }
+    }
+
+    /// Angular velocity of the input shaft in the currently selected gear
+    pub fn input_angular_vel(&self) -> crate::units::RadPerSec {
+        match self {
+            Self::Manual(transmission) => transmission.input_angular_vel(),
+        }
+    }
+
+    /// Inertia of everything behind the transmission, as felt at the input shaft, zero in neutral
+    pub fn effective_inertia(&self) -> f32 {
+        match self {
+            Self::Manual(transmission) => transmission.effective_inertia(),
+        }
+    }
+
+    /// Returns the angular velocity of the input shaft
+    pub fn update(&mut self, delta_s: f32, input: &crate::wheels::WheelInput, torque_in: crate::units::NewtonMetres) -> crate::units::RadPerSec {
+        match self {
+            Self::Manual(transmission) => transmission.update(delta_s, input, torque_in),
+        }
+    }
+}
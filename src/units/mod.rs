@@ -0,0 +1,177 @@
+// Typed units for the values passed between components, so mixing up rad/s and rpm
+// (or a torque and a force) is a compile error rather than a wildly wrong result.
+// Each unit wraps an f32, which can be reached through .0 for the maths inside a component.
+// Vectors, like body velocities and forces, stay plain arrays in SI units.
+// Converting between units is always explicit, through From or one of the radius based helpers.
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f32);
+
+        impl $name {
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            pub fn signum(self) -> f32 {
+                self.0.signum()
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0))
+            }
+
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0))
+            }
+
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                Self(self.0.clamp(min.0, max.0))
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = Self;
+            fn mul(self, factor: f32) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Mul<$name> for f32 {
+            type Output = $name;
+            fn mul(self, value: $name) -> $name {
+                $name(self * value.0)
+            }
+        }
+
+        impl Div<f32> for $name {
+            type Output = Self;
+            fn div(self, divisor: f32) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+
+        /// Dividing two values of the same unit gives a plain ratio
+        impl Div for $name {
+            type Output = f32;
+            fn div(self, divisor: Self) -> f32 {
+                self.0 / divisor.0
+            }
+        }
+
+        impl std::iter::Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|value| value.0).sum())
+            }
+        }
+    };
+}
+
+unit!(
+    /// Angular velocity in radians per second, what the drivetrain works in
+    RadPerSec
+);
+unit!(
+    /// Angular velocity in revolutions per minute, what engine data is specified in
+    Rpm
+);
+unit!(
+    /// Torque in newton metres
+    NewtonMetres
+);
+unit!(
+    /// Force in newtons
+    Newtons
+);
+unit!(
+    /// Speed in metres per second
+    MetresPerSec
+);
+
+/// Converts angular velocity in rad/s to rpm
+const RAD_S_TO_RPM: f32 = 60.0 / (2.0 * std::f32::consts::PI);
+
+impl From<Rpm> for RadPerSec {
+    fn from(rpm: Rpm) -> Self {
+        Self(rpm.0 / RAD_S_TO_RPM)
+    }
+}
+
+impl From<RadPerSec> for Rpm {
+    fn from(angular_vel: RadPerSec) -> Self {
+        Self(angular_vel.0 * RAD_S_TO_RPM)
+    }
+}
+
+impl RadPerSec {
+    /// Speed of the edge of something spinning at this rate, radius in metres
+    pub fn surface_speed(self, radius: f32) -> MetresPerSec {
+        MetresPerSec(self.0 * radius)
+    }
+}
+
+impl MetresPerSec {
+    /// Angular velocity at which something with this radius (m) rolls at this speed
+    pub fn rolling_angular_vel(self, radius: f32) -> RadPerSec {
+        RadPerSec(self.0 / radius)
+    }
+}
+
+impl NewtonMetres {
+    /// Force this torque pushes with at a radius (m)
+    pub fn force_at(self, radius: f32) -> Newtons {
+        Newtons(self.0 / radius)
+    }
+}
+
+impl Newtons {
+    /// Torque this force applies at a radius (m)
+    pub fn torque_at(self, radius: f32) -> NewtonMetres {
+        NewtonMetres(self.0 * radius)
+    }
+}
+
+/// Torque times angular velocity gives power, in watts
+impl Mul<RadPerSec> for NewtonMetres {
+    type Output = f32;
+    fn mul(self, angular_vel: RadPerSec) -> f32 {
+        self.0 * angular_vel.0
+    }
+}
@@ -0,0 +1,35 @@
+// A body that only moves in a straight line.
+// Lateral tyre forces and steering are ignored, apart from the part of the
+// tyre force of a steered wheel that still points forward.
+
+use crate::{units::MetresPerSec, wheels::Wheel};
+
+use super::ExternalForces;
+
+#[derive(Debug, Clone, Default)]
+pub struct LongitudinalBody {
+    /// Forward speed
+    pub velocity: MetresPerSec,
+    /// Forward acceleration during the last update (m/s^2)
+    pub acceleration: f32,
+    /// Distance travelled (m)
+    pub distance: f32,
+}
+
+impl LongitudinalBody {
+    pub fn update(&mut self, delta_s: f32, wheels: &[&Wheel], external: &ExternalForces) {
+        let tyre_force = wheels.iter().map(|wheel| wheel.body_force()[0]).sum::<f32>();
+
+        // The wheels integrate the tyre force implicitly and report it at their new speed,
+        // so the body takes exactly that force and both trade the same momentum
+        let mut velocity = self.velocity.0 + (tyre_force + external.grade_force.0) / external.mass * delta_s;
+
+        // Drag and rolling resistance can bring the vehicle to a stop, but never make it move the other way
+        let resisting_delta = external.resisting_force.0 / external.mass * delta_s;
+        velocity -= velocity.signum() * resisting_delta.min(velocity.abs());
+
+        self.acceleration = (velocity - self.velocity.0) / delta_s;
+        self.velocity = MetresPerSec(velocity);
+        self.distance += velocity * delta_s;
+    }
+}
@@ -0,0 +1,99 @@
+pub mod longitudinal_body;
+pub mod planar_body;
+pub mod rigid_body;
+
+// The rigid body that carries the drivetrain and wheels around.
+// It sums up the tyre forces of all wheels, integrates its own motion
+// and feeds its velocity back into the wheels on the next update.
+
+use crate::{
+    units::{MetresPerSec, Newtons, RadPerSec},
+    wheels::Wheel,
+};
+
+/// Forces acting on the body besides the tyres
+#[derive(Debug, Copy, Clone)]
+pub struct ExternalForces {
+    /// Mass of the body and all wheels (kg)
+    pub mass: f32,
+    /// Force along the forward axis of the vehicle from driving on a slope
+    pub grade_force: Newtons,
+    /// Magnitude of the drag and rolling resistance, which always oppose the motion of the vehicle
+    pub resisting_force: Newtons,
+    /// Weight of the body resting on the suspension, only used by bodies that can move vertically
+    pub weight: Newtons,
+}
+
+#[derive(Debug, Clone)]
+pub enum Body {
+    /// Only moves in a straight line
+    Longitudinal(longitudinal_body::LongitudinalBody),
+    /// Moves over a flat plane, turning around its vertical axis
+    Planar(planar_body::PlanarBody),
+    /// Moves freely, including heave, pitch and roll on the suspension
+    RigidBody(rigid_body::RigidBody),
+}
+
+impl Body {
+    /// Velocity of the centre of gravity in the vehicle's own frame, as (forward, left) in m/s
+    pub fn velocity(&self) -> [f32; 2] {
+        match self {
+            Self::Longitudinal(body) => [body.velocity.0, 0.0],
+            Self::Planar(body) => body.velocity,
+            Self::RigidBody(body) => {
+                let [forward, left, _] = body.local_velocity();
+                [forward, left]
+            }
+        }
+    }
+
+    /// Rotation speed around the vertical axis, positive turning left
+    pub fn yaw_rate(&self) -> RadPerSec {
+        match self {
+            Self::Longitudinal(_) => RadPerSec(0.0),
+            Self::Planar(body) => body.yaw_rate,
+            Self::RigidBody(body) => RadPerSec(body.angular_vel[2]),
+        }
+    }
+
+    /// Acceleration of the centre of gravity during the last update in the vehicle's own frame, as (forward, left) in m/s^2
+    pub fn acceleration(&self) -> [f32; 2] {
+        match self {
+            Self::Longitudinal(body) => [body.acceleration, 0.0],
+            Self::Planar(body) => body.acceleration,
+            Self::RigidBody(body) => [body.acceleration[0], body.acceleration[1]],
+        }
+    }
+
+    /// Distance travelled (m)
+    pub fn distance(&self) -> f32 {
+        match self {
+            Self::Longitudinal(body) => body.distance,
+            Self::Planar(body) => body.distance,
+            Self::RigidBody(body) => body.distance,
+        }
+    }
+
+    /// Speed of the centre of gravity in any direction
+    pub fn speed(&self) -> MetresPerSec {
+        let [forward, lateral] = self.velocity();
+        MetresPerSec((forward * forward + lateral * lateral).sqrt())
+    }
+
+    /// Height (m) and vertical velocity of a point on the body relative to its rest position,
+    /// or None if the body cannot move vertically. point is (forward, left) from the centre of gravity
+    pub fn point_motion(&self, point: [f32; 2]) -> Option<(f32, MetresPerSec)> {
+        match self {
+            Self::RigidBody(body) => Some(body.point_motion(point)),
+            _ => None,
+        }
+    }
+
+    pub fn update(&mut self, delta_s: f32, wheels: &[&Wheel], external: &ExternalForces) {
+        match self {
+            Self::Longitudinal(body) => body.update(delta_s, wheels, external),
+            Self::Planar(body) => body.update(delta_s, wheels, external),
+            Self::RigidBody(body) => body.update(delta_s, wheels, external),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// A body that moves over a flat plane: forward, sideways and around its vertical axis.
+// Velocities are integrated in the vehicle's own frame, and then rotated by the
+// heading to move the vehicle around the world.
+
+use crate::{units::RadPerSec, wheels::Wheel};
+
+use super::ExternalForces;
+
+#[derive(Debug, Clone, Default)]
+pub struct PlanarBody {
+    /// Moment of inertia around the vertical axis through the centre of gravity (kg*m^2)
+    pub yaw_inertia: f32,
+
+    /// Position in the world, as (x, y) in metres
+    pub position: [f32; 2],
+    /// Direction the vehicle is facing, counter-clockwise from the world x axis (rad)
+    pub heading: f32,
+    /// Velocity in the vehicle's own frame, as (forward, left) in m/s
+    pub velocity: [f32; 2],
+    /// Rotation speed around the vertical axis, positive turning left
+    pub yaw_rate: RadPerSec,
+    /// Acceleration during the last update in the vehicle's own frame, as (forward, left) in m/s^2
+    pub acceleration: [f32; 2],
+    /// Distance travelled (m)
+    pub distance: f32,
+}
+
+impl PlanarBody {
+    pub fn update(&mut self, delta_s: f32, wheels: &[&Wheel], external: &ExternalForces) {
+        let mut force = [external.grade_force.0, 0.0];
+        let mut yaw_moment = 0.0;
+        // How much the lateral forces change per m/s of sideways velocity and per rad/s of yaw rate
+        let mut lateral_slope = 0.0;
+        let mut yaw_slope = 0.0;
+        for wheel in wheels {
+            let [x, y] = wheel.position;
+            let [forward_force, lateral_force] = wheel.body_force();
+            force[0] += forward_force;
+            force[1] += lateral_force;
+            yaw_moment += x * lateral_force - y * forward_force;
+
+            let wheel_lateral_slope = wheel.lateral_force_slope.min(0.0);
+            lateral_slope += wheel_lateral_slope;
+            yaw_slope += x * x * wheel_lateral_slope;
+        }
+
+        // The wheels integrate the longitudinal tyre forces implicitly and report them at their new speed,
+        // so the body takes exactly those forces and both trade the same momentum.
+        // Nothing spins up against the lateral forces, they are too stiff to integrate explicitly and
+        // are linearised around the current velocity instead
+        let [forward_vel, lateral_vel] = self.velocity;
+        let yaw_rate = self.yaw_rate.0;
+        let lateral_mass = external.mass - lateral_slope * delta_s;
+        let effective_yaw_inertia = self.yaw_inertia - yaw_slope * delta_s;
+
+        // The vehicle's own frame rotates along with it, which turns part of the forward velocity sideways and vice versa
+        let mut velocity = [
+            forward_vel + (force[0] / external.mass + yaw_rate * lateral_vel) * delta_s,
+            lateral_vel + (force[1] / lateral_mass - yaw_rate * forward_vel) * delta_s,
+        ];
+        self.yaw_rate += RadPerSec(yaw_moment / effective_yaw_inertia * delta_s);
+
+        // Drag and rolling resistance can bring the vehicle to a stop, but never make it move the other way
+        let speed = (velocity[0] * velocity[0] + velocity[1] * velocity[1]).sqrt();
+        if speed > 0.0 {
+            let resisting_delta = external.resisting_force.0 / external.mass * delta_s;
+            let scale = (speed - resisting_delta.min(speed)) / speed;
+            velocity[0] *= scale;
+            velocity[1] *= scale;
+        }
+
+        self.acceleration = [
+            (velocity[0] - forward_vel) / delta_s - yaw_rate * lateral_vel,
+            (velocity[1] - lateral_vel) / delta_s + yaw_rate * forward_vel,
+        ];
+        self.velocity = velocity;
+
+        self.heading += self.yaw_rate.0 * delta_s;
+        let (sin, cos) = self.heading.sin_cos();
+        self.position[0] += (velocity[0] * cos - velocity[1] * sin) * delta_s;
+        self.position[1] += (velocity[0] * sin + velocity[1] * cos) * delta_s;
+        self.distance += (velocity[0] * velocity[0] + velocity[1] * velocity[1]).sqrt() * delta_s;
+    }
+}
@@ -0,0 +1,216 @@
+// A body that moves freely in all six degrees of freedom: it can heave, pitch and roll on its
+// suspension on top of everything the planar body does.
+// The linear velocity is kept in the world frame, the angular velocity in the body's own frame,
+// where the inertia tensor stays constant. The world z axis points up.
+
+use crate::{units::MetresPerSec, wheels::Wheel};
+
+use super::ExternalForces;
+
+#[derive(Debug, Clone)]
+pub struct RigidBody {
+    /// Inertia tensor around the centre of gravity in the body's own (forward, left, up) frame (kg*m^2)
+    pub inertia: [[f32; 3]; 3],
+    /// Height of the centre of gravity above the ground at ride height (m), the tyre forces act this far below it
+    pub cog_height: f32,
+
+    /// Position of the centre of gravity in the world, as (x, y, z) in metres, z is relative to ride height
+    pub position: [f32; 3],
+    /// Orientation as a unit quaternion (w, x, y, z)
+    pub orientation: [f32; 4],
+    /// Velocity in the world frame (m/s)
+    pub velocity: [f32; 3],
+    /// Angular velocity in the body's own frame, as (roll, pitch, yaw) rates in rad/s
+    pub angular_vel: [f32; 3],
+    /// Acceleration during the last update in the body's own frame (m/s^2)
+    pub acceleration: [f32; 3],
+    /// Distance travelled (m)
+    pub distance: f32,
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        Self {
+            inertia: [[0.0; 3]; 3],
+            cog_height: 0.0,
+
+            position: [0.0; 3],
+            orientation: [1.0, 0.0, 0.0, 0.0],
+            velocity: [0.0; 3],
+            angular_vel: [0.0; 3],
+            acceleration: [0.0; 3],
+            distance: 0.0,
+        }
+    }
+}
+
+impl RigidBody {
+    /// Velocity in the body's own frame, as (forward, left, up) in m/s
+    pub fn local_velocity(&self) -> [f32; 3] {
+        rotate(conjugate(self.orientation), self.velocity)
+    }
+
+    /// Rotation around the world's vertical axis, counter-clockwise from the world x axis (rad)
+    pub fn heading(&self) -> f32 {
+        let forward = rotate(self.orientation, [1.0, 0.0, 0.0]);
+        forward[1].atan2(forward[0])
+    }
+
+    /// Roll, pitch and yaw angles (rad), applied in the order yaw, pitch, roll.
+    /// Positive roll lifts the left side, positive pitch lowers the nose
+    pub fn euler_angles(&self) -> [f32; 3] {
+        let [w, x, y, z] = self.orientation;
+        [
+            (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y)),
+            (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin(),
+            (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z)),
+        ]
+    }
+
+    /// Height (m) and vertical velocity of a point on the body relative to its rest position.
+    /// point is (forward, left) from the centre of gravity, at the height of the centre of gravity
+    pub fn point_motion(&self, point: [f32; 2]) -> (f32, MetresPerSec) {
+        let local_point = [point[0], point[1], 0.0];
+        let world_offset = rotate(self.orientation, local_point);
+        let local_point_vel = cross(self.angular_vel, local_point);
+        let world_point_vel = rotate(self.orientation, local_point_vel);
+        (
+            self.position[2] + world_offset[2],
+            MetresPerSec(self.velocity[2] + world_point_vel[2]),
+        )
+    }
+
+    pub fn update(&mut self, delta_s: f32, wheels: &[&Wheel], external: &ExternalForces) {
+        let gravity = rotate(conjugate(self.orientation), [0.0, 0.0, -external.weight.0]);
+        let mut force = [external.grade_force.0 + gravity[0], gravity[1], gravity[2]];
+        let mut moment = [0.0; 3];
+        let mut tyre_force = [0.0; 2];
+        // How much the lateral tyre forces change per m/s of sideways velocity and per rad/s of yaw rate
+        let mut lateral_slope = 0.0;
+        let mut yaw_slope = 0.0;
+        for wheel in wheels {
+            let [x, y] = wheel.position;
+
+            let [forward_force, lateral_force] = wheel.body_force();
+            tyre_force[0] += forward_force;
+            tyre_force[1] += lateral_force;
+            moment[2] += x * lateral_force - y * forward_force;
+
+            // Without a suspension, the wheel pushes on the body with the load it was given
+            let suspension_force = [0.0, 0.0, wheel.suspension.map_or(wheel.load, |suspension| suspension.force).0];
+            add(&mut force, suspension_force);
+            add(&mut moment, cross([x, y, 0.0], suspension_force));
+
+            let wheel_lateral_slope = wheel.lateral_force_slope.min(0.0);
+            lateral_slope += wheel_lateral_slope;
+            yaw_slope += x * x * wheel_lateral_slope;
+        }
+
+        // The wheels integrate the longitudinal tyre forces implicitly and report them at their new speed,
+        // so the body takes exactly those forces and both trade the same momentum.
+        // Nothing spins up against the lateral forces, they are too stiff to integrate explicitly and
+        // are linearised around the current velocity instead. This scales down the force they end up applying,
+        // which rolls the body just as much
+        let tyre_force = [tyre_force[0], tyre_force[1] * external.mass / (external.mass - lateral_slope * delta_s)];
+        force[0] += tyre_force[0];
+        force[1] += tyre_force[1];
+        // The tyre forces act on the ground, below the centre of gravity
+        moment[0] += self.cog_height * tyre_force[1];
+        moment[1] -= self.cog_height * tyre_force[0];
+
+        let local_velocity_change = force.map(|f| f / external.mass * delta_s);
+        let last_velocity = self.velocity;
+        add(&mut self.velocity, rotate(self.orientation, local_velocity_change));
+
+        // Drag and rolling resistance can bring the vehicle to a stop, but never make it move the other way
+        let speed = (self.velocity[0] * self.velocity[0] + self.velocity[1] * self.velocity[1]).sqrt();
+        if speed > 0.0 {
+            let resisting_delta = external.resisting_force.0 / external.mass * delta_s;
+            let scale = (speed - resisting_delta.min(speed)) / speed;
+            self.velocity[0] *= scale;
+            self.velocity[1] *= scale;
+        }
+
+        // Euler's equations of rotation, the spinning body's own angular momentum makes it precess
+        let angular_momentum = mul_matrix(self.inertia, self.angular_vel);
+        let net_moment = sub(moment, cross(self.angular_vel, angular_momentum));
+        let mut effective_inertia = self.inertia;
+        effective_inertia[2][2] -= yaw_slope * delta_s;
+        let angular_accel = mul_matrix(invert(effective_inertia), net_moment);
+        for (angular_vel, accel) in self.angular_vel.iter_mut().zip(angular_accel) {
+            *angular_vel += accel * delta_s;
+        }
+
+        let world_accel = sub(self.velocity, last_velocity).map(|v| v / delta_s);
+        self.acceleration = rotate(conjugate(self.orientation), world_accel);
+
+        for (position, velocity) in self.position.iter_mut().zip(self.velocity) {
+            *position += velocity * delta_s;
+        }
+        self.orientation = integrate(self.orientation, self.angular_vel, delta_s);
+        self.distance += (self.velocity[0] * self.velocity[0] + self.velocity[1] * self.velocity[1]).sqrt() * delta_s;
+    }
+}
+
+fn add(a: &mut [f32; 3], b: [f32; 3]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a += b;
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn mul_matrix(m: [[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn invert(m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let determinant = m[0][0] * cofactor(1, 2, 1, 2) - m[0][1] * cofactor(1, 2, 0, 2) + m[0][2] * cofactor(1, 2, 0, 1);
+    let inv_det = 1.0 / determinant;
+    [
+        [cofactor(1, 2, 1, 2) * inv_det, -cofactor(0, 2, 1, 2) * inv_det, cofactor(0, 1, 1, 2) * inv_det],
+        [-cofactor(1, 2, 0, 2) * inv_det, cofactor(0, 2, 0, 2) * inv_det, -cofactor(0, 1, 0, 2) * inv_det],
+        [cofactor(1, 2, 0, 1) * inv_det, -cofactor(0, 2, 0, 1) * inv_det, cofactor(0, 1, 0, 1) * inv_det],
+    ]
+}
+
+fn conjugate(q: [f32; 4]) -> [f32; 4] {
+    [q[0], -q[1], -q[2], -q[3]]
+}
+
+/// Rotates v by the unit quaternion q
+fn rotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+    let u = [q[1], q[2], q[3]];
+    let t = cross(u, v).map(|c| c * 2.0);
+    let ut = cross(u, t);
+    [
+        v[0] + q[0] * t[0] + ut[0],
+        v[1] + q[0] * t[1] + ut[1],
+        v[2] + q[0] * t[2] + ut[2],
+    ]
+}
+
+/// Rotates q by an angular velocity in its own frame over delta_s, keeping it normalised
+fn integrate(q: [f32; 4], angular_vel: [f32; 3], delta_s: f32) -> [f32; 4] {
+    let [w, x, y, z] = q;
+    let [p, r, s] = angular_vel.map(|v| v * 0.5 * delta_s);
+    let q = [
+        w - x * p - y * r - z * s,
+        x + w * p + y * s - z * r,
+        y + w * r + z * p - x * s,
+        z + w * s + x * r - y * p,
+    ];
+    let length = q.iter().map(|c| c * c).sum::<f32>().sqrt();
+    q.map(|c| c / length)
+}
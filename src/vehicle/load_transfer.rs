@@ -0,0 +1,85 @@
+// Distributes the weight of the vehicle body over its wheels, including the weight
+// transfer caused by accelerating, braking, cornering and driving on a slope.
+// The wheelbase and track width are derived from the positions of the wheels.
+
+use crate::{units::Newtons, GRAVITY};
+
+#[derive(Debug, Copy, Clone)]
+pub struct LoadTransfer {
+    /// Height of the centre of gravity above the ground (m)
+    pub cog_height: f32,
+    /// Fraction of the lateral weight transfer taken by the front axle (0-1),
+    /// determined by the roll stiffness of the front and rear suspension
+    pub front_roll_distribution: f32,
+}
+
+impl LoadTransfer {
+    /// Returns the vertical load on each wheel, in the same order as positions.
+    /// mass:               kg, of everything carried by the wheels
+    /// positions:          wheel contact patches relative to the centre of gravity, as (forward, left) in metres
+    /// longitudinal_accel: m/s^2, positive forward
+    /// lateral_accel:      m/s^2, positive to the left
+    /// road_grade:         radians, positive uphill
+    pub fn wheel_loads(&self, mass: f32, positions: &[[f32; 2]], longitudinal_accel: f32, lateral_accel: f32, road_grade: f32) -> Vec<Newtons> {
+        let weight = mass * GRAVITY * road_grade.cos();
+
+        let front: Vec<usize> = (0..positions.len()).filter(|&i| positions[i][0] >= 0.0).collect();
+        let rear: Vec<usize> = (0..positions.len()).filter(|&i| positions[i][0] < 0.0).collect();
+        if front.is_empty() || rear.is_empty() {
+            // Without wheels on both sides of the centre of gravity, there is no wheelbase to work with
+            return vec![Newtons(weight / positions.len() as f32); positions.len()];
+        }
+
+        let axle_position = |wheels: &[usize]| wheels.iter().map(|&i| positions[i][0]).sum::<f32>() / wheels.len() as f32;
+        let front_axle = axle_position(&front);
+        let rear_axle = axle_position(&rear);
+        let wheelbase = front_axle - rear_axle;
+
+        // Driving up a slope pulls the weight backwards, just like accelerating does
+        let longitudinal_accel = longitudinal_accel + GRAVITY * road_grade.sin();
+        let longitudinal_transfer = mass * longitudinal_accel * self.cog_height / wheelbase;
+        let front_load = weight * -rear_axle / wheelbase - longitudinal_transfer;
+        let rear_load = weight * front_axle / wheelbase + longitudinal_transfer;
+        // An axle that lifts off the ground carries nothing, the other axle takes all of the weight instead
+        let (front_load, rear_load) = if front_load < 0.0 {
+            (0.0, weight)
+        } else if rear_load < 0.0 {
+            (weight, 0.0)
+        } else {
+            (front_load, rear_load)
+        };
+
+        let lateral_transfer = mass * lateral_accel * self.cog_height;
+        let front_roll_distribution = self.front_roll_distribution.clamp(0.0, 1.0);
+
+        let mut loads = vec![Newtons(0.0); positions.len()];
+        for (wheels, axle_load, roll_distribution) in [
+            (&front, front_load, front_roll_distribution),
+            (&rear, rear_load, 1.0 - front_roll_distribution),
+        ] {
+            // Cornering to the left pushes the weight onto the right wheels. Each wheel takes
+            // a share proportional to its distance from the centreline, which balances the roll moment
+            let axle_roll_moment = lateral_transfer * roll_distribution;
+            let lateral_spread = wheels.iter().map(|&i| positions[i][1] * positions[i][1]).sum::<f32>();
+
+            for &i in wheels.iter() {
+                let lateral_load = if lateral_spread > 0.0 {
+                    -axle_roll_moment * positions[i][1] / lateral_spread
+                } else {
+                    0.0
+                };
+                loads[i] = Newtons(axle_load / wheels.len() as f32 + lateral_load);
+            }
+
+            // A wheel that has lifted off the ground carries nothing, the wheels still on the ground
+            // take over what it would have had to pull down, so the axle keeps carrying its full load
+            let lifted = wheels.iter().map(|&i| loads[i].0.min(0.0)).sum::<f32>();
+            let grounded = wheels.iter().map(|&i| loads[i].0.max(0.0)).sum::<f32>();
+            for &i in wheels.iter() {
+                let load = loads[i].0.max(0.0);
+                loads[i] = Newtons(if grounded > 0.0 { load + lifted * load / grounded } else { axle_load / wheels.len() as f32 });
+            }
+        }
+        loads
+    }
+}
@@ -0,0 +1,155 @@
+pub mod body;
+pub mod load_transfer;
+pub mod steering;
+
+// A complete vehicle: the drivetrain and wheels, carried around by a body.
+// The vehicle spreads the weight of the body over the wheels, steers them,
+// and passes the tyre forces on to the body.
+
+use crate::{
+    engine::EngineContainer,
+    ground::GroundRegistry,
+    suspension::AntiRollBar,
+    units::{MetresPerSec, NewtonMetres, Newtons},
+    vehicle::body::ExternalForces,
+    wheels::{Wheel, WheelInput},
+    GRAVITY,
+};
+
+pub struct Vehicle {
+    /// Mass of the body, excluding the wheels and fuel (kg)
+    pub mass: f32,
+    /// Aerodynamic drag coefficient (Cd)
+    pub drag_coefficient: f32,
+    /// Frontal area (m^2)
+    pub frontal_area: f32,
+    /// Density of the air (kg/m^3)
+    pub air_density: f32,
+    /// Rolling resistance coefficient of the tyres, the surface adds its own on top of this
+    pub rolling_resistance: f32,
+    /// Road grade as an angle (radians), positive is uphill
+    pub road_grade: f32,
+    /// Fraction of brake torque going to the front axle (0-1), 0.5 is even
+    pub brake_bias: f32,
+    /// Spreads the weight of the body over the wheels
+    pub load_transfer: load_transfer::LoadTransfer,
+    /// Anti-roll bars, indexing into wheels()
+    pub anti_roll_bars: Vec<AntiRollBar>,
+    /// Steers the wheels in front of the centre of gravity
+    pub steering: steering::Steering,
+
+    /// Determines how the vehicle can move
+    pub body: body::Body,
+
+    pub drivetrain: EngineContainer,
+    /// Wheels that are not connected to the engine
+    pub free_wheels: Vec<Wheel>,
+}
+
+impl Vehicle {
+    /// Mass of the body and the fuel it carries, everything resting on the suspension (kg)
+    pub fn body_mass(&self) -> f32 {
+        self.mass + self.drivetrain.fuel_mass()
+    }
+
+    /// Mass of the body, fuel and all wheels (kg)
+    pub fn total_mass(&self) -> f32 {
+        self.body_mass() + self.wheels().iter().map(|wheel| wheel.mass).sum::<f32>()
+    }
+
+    /// All wheels, starting with the driven wheels
+    pub fn wheels(&self) -> Vec<&Wheel> {
+        let mut wheels = self.drivetrain.wheels();
+        wheels.extend(self.free_wheels.iter());
+        wheels
+    }
+
+    pub fn wheels_mut(&mut self) -> Vec<&mut Wheel> {
+        let mut wheels = self.drivetrain.wheels_mut();
+        wheels.extend(self.free_wheels.iter_mut());
+        wheels
+    }
+
+    /// Puts a wheel, indexing into wheels(), on the material of that name in the registry.
+    /// Returns false if the registry does not know the material, leaving the wheel where it is
+    pub fn set_surface(&mut self, wheel: usize, registry: &GroundRegistry, name: &str) -> bool {
+        match (self.wheels_mut().into_iter().nth(wheel), registry.get(name)) {
+            (Some(wheel), Some(material)) => {
+                wheel.surface = material;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn drag_force(&self) -> Newtons {
+        let speed = self.body.speed().0;
+        Newtons(0.5 * self.air_density * self.drag_coefficient * self.frontal_area * speed * speed)
+    }
+
+    pub fn update(&mut self, delta_s: f32, throttle_input: f32, brake_input: f32, parking_brake_input: f32) {
+        let [vehicle_speed, lateral_speed] = self.body.velocity();
+        let input = WheelInput {
+            vehicle_speed: MetresPerSec(vehicle_speed),
+            lateral_speed: MetresPerSec(lateral_speed),
+            yaw_rate: self.body.yaw_rate(),
+            brake: brake_input,
+            parking_brake: parking_brake_input,
+            brake_bias: self.brake_bias,
+        };
+
+        let positions: Vec<[f32; 2]> = self.wheels().iter().map(|wheel| wheel.position).collect();
+        let [longitudinal_accel, lateral_accel] = self.body.acceleration();
+        // The body gets lighter as the fuel is burned
+        let body_mass = self.body_mass();
+        let loads = self.load_transfer.wheel_loads(body_mass, &positions, longitudinal_accel, lateral_accel, self.road_grade);
+        // The part of the body resting on each corner at rest, used as the sprung mass of its suspension
+        let static_loads = self.load_transfer.wheel_loads(body_mass, &positions, 0.0, 0.0, self.road_grade);
+
+        let mut anti_roll_forces = vec![Newtons(0.0); positions.len()];
+        {
+            let travels: Vec<f32> = self.wheels().iter().map(|wheel| wheel.suspension.map_or(0.0, |s| s.travel())).collect();
+            for bar in &self.anti_roll_bars {
+                let (left, right) = bar.forces(travels[bar.left], travels[bar.right]);
+                anti_roll_forces[bar.left] += left;
+                anti_roll_forces[bar.right] += right;
+            }
+        }
+
+        let steer_angles = self.steering.wheel_angles(&positions);
+
+        // A body that can move vertically sits on the suspension by itself, instead of pushing down with the transferred load
+        let corner_motions: Vec<Option<(f32, MetresPerSec)>> = positions.iter().map(|&position| self.body.point_motion(position)).collect();
+
+        for (i, wheel) in self.wheels_mut().into_iter().enumerate() {
+            wheel.steer_angle = steer_angles[i];
+            let wheel_mass = wheel.mass;
+            let sprung_mass = static_loads[i].0 / GRAVITY;
+            let normal_force = match (wheel.suspension.as_mut(), corner_motions[i]) {
+                (Some(suspension), Some((height, vel))) => Some(suspension.update_mounted(delta_s, height, vel, sprung_mass, wheel_mass, anti_roll_forces[i])),
+                (Some(suspension), None) => Some(suspension.update(delta_s, loads[i], sprung_mass, wheel_mass, anti_roll_forces[i])),
+                (None, _) => None,
+            };
+            // The suspension reports the full force on the road, the wheel adds its own weight back on top
+            wheel.load = normal_force.map_or(loads[i], |force| force - Newtons(wheel_mass * GRAVITY));
+        }
+
+        self.drivetrain.update(delta_s, &input, throttle_input);
+        for wheel in self.free_wheels.iter_mut() {
+            wheel.update(delta_s, &input, NewtonMetres(0.0));
+        }
+
+        let total_mass = self.total_mass();
+        let rolling_resistance_force = Newtons(self.rolling_resistance * total_mass * GRAVITY * self.road_grade.cos());
+        let external = ExternalForces {
+            mass: total_mass,
+            grade_force: Newtons(-total_mass * GRAVITY * self.road_grade.sin()),
+            resisting_force: self.drag_force() + rolling_resistance_force,
+            weight: Newtons(body_mass * GRAVITY * self.road_grade.cos()),
+        };
+
+        let mut wheels = self.drivetrain.wheels();
+        wheels.extend(self.free_wheels.iter());
+        self.body.update(delta_s, &wheels, &external);
+    }
+}
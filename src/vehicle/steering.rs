@@ -0,0 +1,41 @@
+// Turns the steering input into a steering angle for each front wheel.
+// With Ackermann geometry the inner wheel steers further than the outer wheel,
+// so both roll around the same point on the line through the rear axle.
+
+#[derive(Debug, Copy, Clone)]
+pub struct Steering {
+    /// Steering angle of the wheels at full lock, for parallel steering (rad)
+    pub max_angle: f32,
+    /// 1 is full Ackermann geometry, 0 is parallel steering, anything above 1 is over-Ackermann
+    pub ackermann: f32,
+    /// Steering input (-1 to 1), positive to the left
+    pub input: f32,
+}
+
+impl Steering {
+    /// Returns the steering angle of each wheel (rad), in the same order as positions.
+    /// Only the wheels in front of the centre of gravity are steered.
+    /// positions: wheel contact patches relative to the centre of gravity, as (forward, left) in metres
+    pub fn wheel_angles(&self, positions: &[[f32; 2]]) -> Vec<f32> {
+        let angle = self.input.clamp(-1.0, 1.0) * self.max_angle;
+
+        let rear: Vec<f32> = positions.iter().map(|p| p[0]).filter(|&x| x < 0.0).collect();
+        if rear.is_empty() || angle == 0.0 {
+            return positions.iter().map(|p| if p[0] >= 0.0 { angle } else { 0.0 }).collect();
+        }
+        let rear_axle = rear.iter().sum::<f32>() / rear.len() as f32;
+        let front: Vec<f32> = positions.iter().map(|p| p[0]).filter(|&x| x >= 0.0).collect();
+        let front_axle = front.iter().sum::<f32>() / front.len().max(1) as f32;
+
+        // The point the vehicle turns around, to the left of the rear axle for a positive angle
+        let turn_radius = (front_axle - rear_axle) / angle.tan();
+
+        positions.iter().map(|&[x, y]| {
+            if x < 0.0 {
+                return 0.0;
+            }
+            let ackermann_angle = ((x - rear_axle) / (turn_radius - y)).atan();
+            angle + (ackermann_angle - angle) * self.ackermann
+        }).collect()
+    }
+}
@@ -0,0 +1,102 @@
+use crate::units::NewtonMetres;
+
+/// Smallest slip ratio the ABS measures the slip error in
+const MIN_TARGET_SLIP: f32 = 0.01;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Axle {
+    Front,
+    Rear,
+}
+
+/// A brake on a single wheel
+#[derive(Debug, Copy, Clone)]
+pub struct Brake {
+    /// Brake torque at full brake input, with an even brake bias
+    pub max_torque: NewtonMetres,
+    /// Brake torque at full parking brake input, 0 for wheels without a parking brake
+    pub max_parking_torque: NewtonMetres,
+    /// Which side of the brake bias this brake is on
+    pub axle: Axle,
+    /// Optional anti-lock braking controller
+    pub abs: Option<Abs>,
+
+    /// Brake torque applied during the last update
+    pub torque: NewtonMetres,
+}
+
+impl Brake {
+    /// Multiplier on the brake input caused by the brake bias.
+    /// brake_bias is the fraction of brake torque going to the front axle (0-1), where 0.5 is even.
+    /// Like a balance bar, the favoured axle receives full pressure and the other axle is reduced.
+    pub fn bias_factor(&self, brake_bias: f32) -> f32 {
+        let front = brake_bias.clamp(0.0, 1.0);
+        let rear = 1.0 - front;
+        let (this, other) = match self.axle {
+            Axle::Front => (front, rear),
+            Axle::Rear => (rear, front),
+        };
+        if this >= other { 1.0 } else { this / other }
+    }
+
+    /// Returns the magnitude of the brake torque. slip_ratio is the current slip ratio of the wheel
+    pub fn update(&mut self, delta_s: f32, input: &super::WheelInput, slip_ratio: f32) -> NewtonMetres {
+        let mut brake_input = input.brake.clamp(0.0, 1.0) * self.bias_factor(input.brake_bias);
+        if let Some(abs) = &mut self.abs {
+            brake_input *= abs.update(delta_s, brake_input, slip_ratio);
+        }
+        let parking_brake_input = input.parking_brake.clamp(0.0, 1.0);
+
+        self.torque = self.max_torque * brake_input + self.max_parking_torque * parking_brake_input;
+        self.torque
+    }
+}
+
+/// A simple anti-lock braking controller.
+/// It releases brake pressure whenever the wheel slips more than target_slip under braking,
+/// and reapplies it once the wheel has recovered. Both happen in proportion to how far the slip
+/// is from the target, so the pressure settles near it instead of jumping back and forth.
+#[derive(Debug, Copy, Clone)]
+pub struct Abs {
+    /// Braking slip ratio (positive) above which brake pressure is released
+    pub target_slip: f32,
+    /// How quickly brake pressure is released, as a fraction of full pressure per second,
+    /// for every target_slip the wheel slips past it
+    pub release_rate: f32,
+    /// How quickly brake pressure is reapplied with the wheel rolling freely, as a fraction of full pressure per second
+    pub apply_rate: f32,
+
+    /// Fraction of the brake input that is let through (0-1)
+    pub modulation: f32,
+    /// Whether ABS is currently releasing brake pressure
+    pub active: bool,
+}
+
+impl Abs {
+    pub fn new(target_slip: f32, release_rate: f32, apply_rate: f32) -> Self {
+        Self {
+            target_slip,
+            release_rate,
+            apply_rate,
+
+            modulation: 1.0,
+            active: false,
+        }
+    }
+
+    /// Returns the fraction of the brake input that should be let through
+    pub fn update(&mut self, delta_s: f32, brake_input: f32, slip_ratio: f32) -> f32 {
+        // Braking slip is negative going forward and positive in reverse, either way the wheel spins
+        // slower than the ground below it
+        // The error is measured in steps of target_slip, which is kept away from zero so the steps never vanish
+        let slip_error = (slip_ratio.abs() - self.target_slip) / self.target_slip.max(MIN_TARGET_SLIP);
+        self.active = brake_input > 0.0 && slip_error > 0.0;
+        if self.active {
+            self.modulation -= self.release_rate * slip_error * delta_s;
+        } else {
+            self.modulation += self.apply_rate * (-slip_error).min(1.0) * delta_s;
+        }
+        self.modulation = self.modulation.clamp(0.0, 1.0);
+        self.modulation
+    }
+}
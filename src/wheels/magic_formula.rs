@@ -0,0 +1,241 @@
+// Pacejka Magic Formula tyre models, for use with fitted coefficients.
+// Camber is not simulated, so all camber dependent terms are left out (camber = 0).
+// All models take the slip ratio as a fraction, the slip angle in radians and the load in N,
+// and convert to whatever units the coefficients were fitted in.
+
+use crate::units::Newtons;
+
+use super::TyreModel;
+
+fn magic_formula(b: f32, c: f32, d: f32, e: f32, x: f32) -> f32 {
+    let bx = b * x;
+    d * (c * (bx - e * (bx - bx.atan())).atan()).sin()
+}
+
+/// Pacejka '89, as used by many simulators.
+/// Load is in kN, slip ratio in percent, slip angle in degrees.
+#[derive(Debug, Copy, Clone)]
+pub struct Pacejka89 {
+    /// Longitudinal coefficients b0 - b10
+    pub b: [f32; 11],
+    /// Lateral coefficients a0 - a13
+    pub a: [f32; 14],
+}
+
+impl TyreModel for Pacejka89 {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        let b = &self.b;
+        let fz = load.0 / 1000.0;
+        let slip = slip_ratio * 100.0;
+
+        let c = b[0];
+        let d = fz * (b[1] * fz + b[2]);
+        let bcd = (b[3] * fz * fz + b[4] * fz) * (-b[5] * fz).exp();
+        let bb = bcd / (c * d);
+        let e = b[6] * fz * fz + b[7] * fz + b[8];
+        let h = b[9] * fz + b[10];
+        Newtons(magic_formula(bb, c, d, e, slip + h))
+    }
+
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        let a = &self.a;
+        let fz = load.0 / 1000.0;
+        let slip = slip_angle.to_degrees();
+
+        let c = a[0];
+        let d = fz * (a[1] * fz + a[2]);
+        let bcd = a[3] * (2.0 * (fz / a[4]).atan()).sin();
+        let b = bcd / (c * d);
+        let e = a[6] * fz + a[7];
+        let h = a[9] * fz + a[10];
+        let v = a[12] * fz + a[13];
+        Newtons(magic_formula(b, c, d, e, slip + h) + v)
+    }
+}
+
+/// Pacejka '94.
+/// Load is in kN, slip ratio in percent, slip angle in degrees.
+#[derive(Debug, Copy, Clone)]
+pub struct Pacejka94 {
+    /// Longitudinal coefficients b0 - b13
+    pub b: [f32; 14],
+    /// Lateral coefficients a0 - a17
+    pub a: [f32; 18],
+}
+
+impl TyreModel for Pacejka94 {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        let b = &self.b;
+        let fz = load.0 / 1000.0;
+        let slip = slip_ratio * 100.0;
+
+        let c = b[0];
+        let d = fz * (b[1] * fz + b[2]);
+        let bcd = (b[3] * fz * fz + b[4] * fz) * (-b[5] * fz).exp();
+        let bb = bcd / (c * d);
+        let h = b[9] * fz + b[10];
+        let v = b[11] * fz + b[12];
+        let e = (b[6] * fz * fz + b[7] * fz + b[8]) * (1.0 - b[13] * (slip + h).signum());
+        Newtons(magic_formula(bb, c, d, e, slip + h) + v)
+    }
+
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        let a = &self.a;
+        let fz = load.0 / 1000.0;
+        let slip = slip_angle.to_degrees();
+
+        let c = a[0];
+        let d = fz * (a[1] * fz + a[2]);
+        let bcd = a[3] * (2.0 * (fz / a[4]).atan()).sin();
+        let b = bcd / (c * d);
+        let h = a[8] * fz + a[9];
+        let v = a[11] * fz + a[12];
+        let e = (a[6] * fz + a[7]) * (1.0 - a[17] * (slip + h).signum());
+        Newtons(magic_formula(b, c, d, e, slip + h) + v)
+    }
+}
+
+/// MF 5.2, using the coefficient names found in .tir files.
+/// Everything is in SI units. Scaling factors (lambda) are all assumed to be 1.
+/// MF 5.2 uses the ISO sign convention, where the slip angle is positive when sliding to the left,
+/// so the slip angle is flipped before it is passed into the formulas.
+#[derive(Debug, Copy, Clone)]
+pub struct Mf52 {
+    /// Nominal load (N)
+    pub fnomin: f32,
+
+    // Pure longitudinal slip
+    pub pcx1: f32,
+    pub pdx1: f32,
+    pub pdx2: f32,
+    pub pex1: f32,
+    pub pex2: f32,
+    pub pex3: f32,
+    pub pex4: f32,
+    pub pkx1: f32,
+    pub pkx2: f32,
+    pub pkx3: f32,
+    pub phx1: f32,
+    pub phx2: f32,
+    pub pvx1: f32,
+    pub pvx2: f32,
+
+    // Combined slip, longitudinal
+    pub rbx1: f32,
+    pub rbx2: f32,
+    pub rcx1: f32,
+    pub rex1: f32,
+    pub rex2: f32,
+    pub rhx1: f32,
+
+    // Pure lateral slip
+    pub pcy1: f32,
+    pub pdy1: f32,
+    pub pdy2: f32,
+    pub pey1: f32,
+    pub pey2: f32,
+    pub pey3: f32,
+    pub pky1: f32,
+    pub pky2: f32,
+    pub phy1: f32,
+    pub phy2: f32,
+    pub pvy1: f32,
+    pub pvy2: f32,
+
+    // Combined slip, lateral
+    pub rby1: f32,
+    pub rby2: f32,
+    pub rby3: f32,
+    pub rcy1: f32,
+    pub rey1: f32,
+    pub rey2: f32,
+    pub rhy1: f32,
+    pub rhy2: f32,
+    pub rvy1: f32,
+    pub rvy2: f32,
+    pub rvy4: f32,
+    pub rvy5: f32,
+    pub rvy6: f32,
+}
+
+impl Mf52 {
+    fn load_increment(&self, load: f32) -> f32 {
+        (load - self.fnomin) / self.fnomin
+    }
+
+    fn lateral_friction_coeff(&self, dfz: f32) -> f32 {
+        self.pdy1 + self.pdy2 * dfz
+    }
+
+    /// alpha is the slip angle using the ISO sign convention
+    fn pure_lateral_force(&self, alpha: f32, load: f32) -> f32 {
+        let dfz = self.load_increment(load);
+
+        let shy = self.phy1 + self.phy2 * dfz;
+        let alpha_y = alpha + shy;
+        let c = self.pcy1;
+        let d = self.lateral_friction_coeff(dfz) * load;
+        let e = ((self.pey1 + self.pey2 * dfz) * (1.0 - self.pey3 * alpha_y.signum())).min(1.0);
+        let k = self.pky1 * self.fnomin * (2.0 * (load / (self.pky2 * self.fnomin)).atan()).sin();
+        let b = k / (c * d);
+        let svy = load * (self.pvy1 + self.pvy2 * dfz);
+        magic_formula(b, c, d, e, alpha_y) + svy
+    }
+}
+
+impl TyreModel for Mf52 {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        let load = load.0;
+        let dfz = self.load_increment(load);
+
+        let shx = self.phx1 + self.phx2 * dfz;
+        let kappa_x = slip_ratio + shx;
+        let c = self.pcx1;
+        let d = (self.pdx1 + self.pdx2 * dfz) * load;
+        let e = ((self.pex1 + self.pex2 * dfz + self.pex3 * dfz * dfz) * (1.0 - self.pex4 * kappa_x.signum())).min(1.0);
+        let k = load * (self.pkx1 + self.pkx2 * dfz) * (self.pkx3 * dfz).exp();
+        let b = k / (c * d);
+        let svx = load * (self.pvx1 + self.pvx2 * dfz);
+        Newtons(magic_formula(b, c, d, e, kappa_x) + svx)
+    }
+
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons {
+        if load.0 <= 0.0 { return Newtons(0.0); }
+        Newtons(self.pure_lateral_force(-slip_angle, load.0))
+    }
+
+    /// Uses the MF 5.2 combined slip weighting functions
+    fn combined_force(&self, slip_ratio: f32, slip_angle: f32, load: Newtons) -> (Newtons, Newtons) {
+        if load.0 <= 0.0 { return (Newtons(0.0), Newtons(0.0)); }
+        let longitudinal = self.longitudinal_force(slip_ratio, load);
+        let load = load.0;
+        let dfz = self.load_increment(load);
+        let alpha = -slip_angle;
+
+        // Weighting function, normalised so it is 1 when the other slip is zero
+        let weight = |b: f32, c: f32, e: f32, sh: f32, slip: f32| {
+            let g = |x: f32| (c * (b * x - e * (b * x - (b * x).atan())).atan()).cos();
+            g(slip + sh) / g(sh)
+        };
+
+        let bx_alpha = self.rbx1 * (self.rbx2 * slip_ratio).atan().cos();
+        let ex_alpha = self.rex1 + self.rex2 * dfz;
+        let gx_alpha = weight(bx_alpha, self.rcx1, ex_alpha, self.rhx1, alpha);
+        let longitudinal = gx_alpha * longitudinal;
+
+        let by_kappa = self.rby1 * (self.rby2 * (alpha - self.rby3)).atan().cos();
+        let ey_kappa = self.rey1 + self.rey2 * dfz;
+        let shy_kappa = self.rhy1 + self.rhy2 * dfz;
+        let gy_kappa = weight(by_kappa, self.rcy1, ey_kappa, shy_kappa, slip_ratio);
+        let dvy_kappa = self.lateral_friction_coeff(dfz) * load * (self.rvy1 + self.rvy2 * dfz) * (self.rvy4 * alpha).atan().cos();
+        let svy_kappa = dvy_kappa * (self.rvy5 * (self.rvy6 * slip_ratio).atan()).sin();
+        let lateral = gy_kappa * self.pure_lateral_force(alpha, load) + svy_kappa;
+
+        (longitudinal, Newtons(lateral))
+    }
+}
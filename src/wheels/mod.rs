@@ -2,17 +2,143 @@
 // For the reference implementation, see /lua/vehicle/wheels.lua in a valid BeamNG.Drive install
 
 pub mod tyre_model;
+pub mod magic_formula;
+pub mod brake;
+
+use crate::units::{MetresPerSec, NewtonMetres, Newtons, RadPerSec};
+
+/// A model that turns tyre slip into forces.
+/// Slip ratio is a fraction, where positive means the wheel spins faster than the ground below it.
+/// Slip angle is in radians, positive when the wheel slides to the right.
+/// Load is the vertical load on the tyre.
+pub trait TyreModel {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons;
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons;
+
+    /// Multiplier on the tyre forces, based on how fast the contact patch slides over the ground.
+    /// Models that already include the friction coefficient in their forces should leave this at 1
+    fn friction_coeff(&self, _sliding_vel: MetresPerSec, _load: Newtons) -> f32 {
+        1.0
+    }
+
+    /// Returns (longitudinal force, lateral force) under combined slip.
+    /// By default, both pure slip curves are evaluated at the length of the whole slip vector,
+    /// and weighted by its direction. This keeps the result on the friction ellipse, and matches
+    /// the pure slip forces when there is only one kind of slip.
+    fn combined_force(&self, slip_ratio: f32, slip_angle: f32, load: Newtons) -> (Newtons, Newtons) {
+        let lateral_slip = slip_angle.tan();
+        let combined_slip = (slip_ratio * slip_ratio + lateral_slip * lateral_slip).sqrt();
+        if combined_slip == 0.0 {
+            return (Newtons(0.0), Newtons(0.0));
+        }
+        (
+            self.longitudinal_force(combined_slip.copysign(slip_ratio), load) * slip_ratio.abs() / combined_slip,
+            self.lateral_force(combined_slip.atan().copysign(slip_angle), load) * lateral_slip.abs() / combined_slip,
+        )
+    }
+}
+
+/// The tyre model used by a wheel
+#[derive(Debug, Copy, Clone)]
+pub enum Tyre {
+    BeamNG(tyre_model::TyreData),
+    Pacejka89(magic_formula::Pacejka89),
+    Pacejka94(magic_formula::Pacejka94),
+    Mf52(magic_formula::Mf52),
+}
+
+impl TyreModel for Tyre {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons {
+        match self {
+            Self::BeamNG(tyre) => tyre.longitudinal_force(slip_ratio, load),
+            Self::Pacejka89(tyre) => tyre.longitudinal_force(slip_ratio, load),
+            Self::Pacejka94(tyre) => tyre.longitudinal_force(slip_ratio, load),
+            Self::Mf52(tyre) => tyre.longitudinal_force(slip_ratio, load),
+        }
+    }
+
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons {
+        match self {
+            Self::BeamNG(tyre) => tyre.lateral_force(slip_angle, load),
+            Self::Pacejka89(tyre) => tyre.lateral_force(slip_angle, load),
+            Self::Pacejka94(tyre) => tyre.lateral_force(slip_angle, load),
+            Self::Mf52(tyre) => tyre.lateral_force(slip_angle, load),
+        }
+    }
+
+    fn friction_coeff(&self, sliding_vel: MetresPerSec, load: Newtons) -> f32 {
+        match self {
+            Self::BeamNG(tyre) => tyre.friction_coeff(sliding_vel, load),
+            Self::Pacejka89(tyre) => tyre.friction_coeff(sliding_vel, load),
+            Self::Pacejka94(tyre) => tyre.friction_coeff(sliding_vel, load),
+            Self::Mf52(tyre) => tyre.friction_coeff(sliding_vel, load),
+        }
+    }
+
+    fn combined_force(&self, slip_ratio: f32, slip_angle: f32, load: Newtons) -> (Newtons, Newtons) {
+        match self {
+            Self::BeamNG(tyre) => tyre.combined_force(slip_ratio, slip_angle, load),
+            Self::Pacejka89(tyre) => tyre.combined_force(slip_ratio, slip_angle, load),
+            Self::Pacejka94(tyre) => tyre.combined_force(slip_ratio, slip_angle, load),
+            Self::Mf52(tyre) => tyre.combined_force(slip_ratio, slip_angle, load),
+        }
+    }
+}
+
+/// Inputs passed down through the drivetrain to every wheel.
+/// Velocities are those of the vehicle's centre of gravity, in the vehicle's own frame.
+/// Each wheel derives the velocity of its own contact patch from these and its position.
+#[derive(Debug, Copy, Clone)]
+pub struct WheelInput {
+    /// Forward speed of the vehicle
+    pub vehicle_speed: MetresPerSec,
+    /// Sideways speed of the vehicle, positive to the left
+    pub lateral_speed: MetresPerSec,
+    /// Rotation speed of the vehicle around its vertical axis, positive turning left
+    pub yaw_rate: RadPerSec,
+    /// Brake pedal input (0-1)
+    pub brake: f32,
+    /// Parking brake input (0-1)
+    pub parking_brake: f32,
+    /// Fraction of brake torque going to the front axle (0-1), 0.5 is even
+    pub brake_bias: f32,
+}
+
+impl Default for WheelInput {
+    fn default() -> Self {
+        Self {
+            vehicle_speed: MetresPerSec(0.0),
+            lateral_speed: MetresPerSec(0.0),
+            yaw_rate: RadPerSec(0.0),
+            brake: 0.0,
+            parking_brake: 0.0,
+            brake_bias: 0.5,
+        }
+    }
+}
 
 #[derive(Debug, Copy, Clone)]
 pub struct Wheel {
-    /// Tyre data
-    pub tyre: tyre_model::TyreData,
+    /// Tyre model
+    pub tyre: Tyre,
     /// Determines the wheel direction, to differentiate between left and right wheels
     pub direction: f32,
     /// The radius of the wheel, including tyre
     pub radius: f32,
     /// The mass of the wheel, including tyre, in kg
     pub mass: f32,
+    /// Position of the contact patch relative to the centre of gravity, as (forward, left) in metres
+    pub position: [f32; 2],
+    /// Steering angle relative to the vehicle, positive to the left (rad)
+    pub steer_angle: f32,
+    /// The brake on this wheel
+    pub brake: brake::Brake,
+    /// Vertical load the vehicle puts on this wheel, excluding the weight of the wheel itself
+    pub load: Newtons,
+    /// The ground the wheel is currently on
+    pub surface: crate::ground::GroundMaterial,
+    /// Suspension of this corner, without one the wheel is mounted rigidly to the body
+    pub suspension: Option<crate::suspension::Suspension>,
 
     /// Deflated, does not imply broken!
     pub deflated: bool,
@@ -21,58 +147,208 @@ pub struct Wheel {
 
     /// Updated whenever calc_wheel_accel_torque is called
     pub last_slip: f32,
+    /// Slip angle in radians, updated whenever calc_wheel_accel_torque is called
+    pub last_slip_angle: f32,
+    /// Force pushing the wheel forward, updated whenever calc_wheel_accel_torque is called
+    pub longitudinal_force: Newtons,
+    /// Force pushing the wheel to its left, updated whenever calc_wheel_accel_torque is called
+    pub lateral_force: Newtons,
+    /// How much the lateral force changes per m/s of sideways ground speed, updated whenever calc_wheel_accel_torque is called
+    pub lateral_force_slope: f32,
+    /// Velocity of the contact patch over the ground in the wheel's own frame, as (forward, left) in m/s,
+    /// updated whenever calc_wheel_accel_torque is called
+    pub contact_vel: [f32; 2],
+    /// Speed at which the contact patch slides over the ground, updated whenever calc_wheel_accel_torque is called
+    pub sliding_vel: MetresPerSec,
+    /// Friction coefficient of the tyre and ground combined, updated whenever calc_wheel_accel_torque is called
+    pub friction_coeff: f32,
+    /// How deep the tyre has sunk into the ground (m), updated whenever update is called
+    pub sinkage: f32,
+
+    /// Inertia of the wheel including the linearised tyre force, as felt by the drivetrain during the last update
+    pub effective_inertia: f32,
 
-    pub last_angular_vel: f32,
+    pub last_angular_vel: RadPerSec,
 
-    pub angular_vel: f32,
+    pub angular_vel: RadPerSec,
 
-    pub wheel_speed: f32,
+    pub wheel_speed: MetresPerSec,
 }
 
 impl Wheel {
-    /// Returns the angular velocity of the wheel
-    pub fn update(&mut self, delta_s: f32, vehicle_speed: f32, torque_in: f32) -> f32 {
-        if self.broken { return 0.0; } // Return early if the wheel is broken
+    pub fn new(tyre: Tyre, direction: f32, radius: f32, mass: f32, brake: brake::Brake) -> Self {
+        Self {
+            tyre,
+            direction,
+            radius,
+            mass,
+            position: [0.0, 0.0],
+            steer_angle: 0.0,
+            brake,
+            load: Newtons(0.0),
+            surface: crate::ground::GroundMaterial::ASPHALT,
+            suspension: None,
+
+            deflated: false,
+            broken: false,
 
-        // let brake_input = brake_input.min(1.0);
-        // let parking_brake_input = parking_brake_input.min(1.0);
+            last_slip: 0.0,
+            last_slip_angle: 0.0,
+            longitudinal_force: Newtons(0.0),
+            lateral_force: Newtons(0.0),
+            lateral_force_slope: 0.0,
+            contact_vel: [0.0, 0.0],
+            sliding_vel: MetresPerSec(0.0),
+            friction_coeff: 0.0,
+            sinkage: 0.0,
 
-        let accel_torque = self.calc_wheel_accel_torque(vehicle_speed, torque_in);
-        // TODO: Brake torque? Seems like we can just calculate the brake torque and add it to this
-        let total_torque = (torque_in - accel_torque).max(0.0);
+            effective_inertia: mass * (radius * radius) / 2.0,
+
+            last_angular_vel: RadPerSec(0.0),
+            angular_vel: RadPerSec(0.0),
+            wheel_speed: MetresPerSec(0.0),
+        }
+    }
 
-        self.update_wheel_velocity(delta_s, total_torque);
+    /// Total vertical load on the tyre, including the weight of the wheel itself
+    pub fn normal_load(&self) -> Newtons {
+        self.load + Newtons(self.mass * crate::GRAVITY)
+    }
+
+    pub fn inertia(&self) -> f32 {
+        self.mass * (self.radius * self.radius) / 2.0
+    }
+
+    /// Returns the angular velocity of the wheel
+    pub fn update(&mut self, delta_s: f32, input: &WheelInput, torque_in: NewtonMetres) -> RadPerSec {
+        // A broken wheel no longer turns with the drivetrain or pushes on the road
+        if self.broken {
+            self.longitudinal_force = Newtons(0.0);
+            self.lateral_force = Newtons(0.0);
+            self.lateral_force_slope = 0.0;
+            return RadPerSec(0.0);
+        }
+
+        let (accel_torque, accel_torque_slope) = self.calc_wheel_accel_torque(input);
+        let brake_torque = self.brake.update(delta_s, input, self.last_slip);
+        let rolling_resistance_torque = self.calc_rolling_resistance_torque();
+
+        self.update_wheel_velocity(delta_s, torque_in - accel_torque, accel_torque_slope, brake_torque + rolling_resistance_torque);
 
         self.angular_vel
     }
 
-    // TODO: Use the friction coefficients calculated somehow?
-    // TODO: Incorporate tyre model into friction coefficient calculations
-    // TODO: Incorporate ground model friction coefficient
-    // TODO: I think vehicle speed needs to be the individual wheel speed here.
-    //       This should be good enough for testing, but it's not correct!
-    fn calc_wheel_accel_torque(&mut self, vehicle_speed: f32, torque_in: f32) -> f32 {
-        let tyre_friction_coefficient = 0.9; // TODO: This should be calculated using the tyre model
-        let ground_mat_friction_coefficient = 1.0;
-        let friction_coefficient = tyre_friction_coefficient * ground_mat_friction_coefficient;
+    /// Velocity of the contact patch over the ground in the wheel's own frame, as (forward, left) in m/s
+    pub fn calc_contact_vel(&self, input: &WheelInput) -> [f32; 2] {
+        let [x, y] = self.position;
+        let forward_vel = input.vehicle_speed.0 - input.yaw_rate.0 * y;
+        let lateral_vel = input.lateral_speed.0 + input.yaw_rate.0 * x;
 
-        // We introduce a tiny minimum speed to prevent division by zero
-        // Same for the angular velocity
-        const EPSILON: f32 = 0.01;
-        let slip_ratio = (self.last_angular_vel.abs() * self.radius).min(EPSILON) / vehicle_speed.abs().max(EPSILON);
+        let (sin, cos) = self.steer_angle.sin_cos();
+        [
+            forward_vel * cos + lateral_vel * sin,
+            lateral_vel * cos - forward_vel * sin,
+        ]
+    }
+
+    /// Tyre force in the vehicle's frame, as (forward, left) in N
+    pub fn body_force(&self) -> [f32; 2] {
+        let (sin, cos) = self.steer_angle.sin_cos();
+        let (longitudinal_force, lateral_force) = (self.longitudinal_force.0, self.lateral_force.0);
+        [
+            longitudinal_force * cos - lateral_force * sin,
+            longitudinal_force * sin + lateral_force * cos,
+        ]
+    }
+
+    fn calc_slip(&self, contact_vel: [f32; 2], angular_vel: f32) -> (f32, f32) {
+        // We introduce a minimum speed to prevent division by zero. This also keeps the slip
+        // from jumping between -1 and 1 around standstill, which would make the vehicle jitter
+        const LOW_SPEED: f32 = 0.5;
+        let [forward_vel, lateral_vel] = contact_vel;
+        let slip_ratio = (angular_vel * self.radius - forward_vel) / forward_vel.abs().max(LOW_SPEED);
+        // Positive when the wheel slides to the right, so it produces a force to the left
+        let slip_angle = (-lateral_vel).atan2(forward_vel.abs().max(LOW_SPEED));
+        (slip_ratio, slip_angle)
+    }
+
+    /// Returns the torque the tyre force puts on the wheel, and how much that torque
+    /// changes per rad/s of wheel speed
+    fn calc_wheel_accel_torque(&mut self, input: &WheelInput) -> (NewtonMetres, f32) {
+        let load = self.normal_load();
+        let angular_vel = self.angular_vel.0;
+        let contact_vel = self.calc_contact_vel(input);
+        let (slip_ratio, slip_angle) = self.calc_slip(contact_vel, angular_vel);
+        let longitudinal_sliding_vel = angular_vel * self.radius - contact_vel[0];
+        let sliding_vel = MetresPerSec((longitudinal_sliding_vel * longitudinal_sliding_vel + contact_vel[1] * contact_vel[1]).sqrt());
+
+        let ground_mat_friction_coefficient = self.surface.friction_coeff(sliding_vel);
+        let friction_coefficient = self.tyre.friction_coeff(sliding_vel, load) * ground_mat_friction_coefficient;
 
         self.last_slip = slip_ratio;
+        self.last_slip_angle = slip_angle;
+        self.contact_vel = contact_vel;
+        self.sliding_vel = sliding_vel;
+        self.friction_coeff = friction_coefficient;
+
+        let (longitudinal_force, lateral_force) = self.tyre.combined_force(slip_ratio, slip_angle, load);
+        self.longitudinal_force = longitudinal_force * friction_coefficient;
+        self.lateral_force = lateral_force * friction_coefficient;
 
-        self.tyre.calculate_accel_force(slip_ratio)
+        // Sample the tyre a tiny bit faster, to see how quickly the force changes with wheel speed
+        const ANGULAR_VEL_STEP: f32 = 0.01;
+        let (slip_ratio_next, _) = self.calc_slip(contact_vel, angular_vel + ANGULAR_VEL_STEP);
+        let (longitudinal_force_next, _) = self.tyre.combined_force(slip_ratio_next, slip_angle, load);
+        let mut force_slope = (longitudinal_force_next - longitudinal_force).0 * friction_coefficient / ANGULAR_VEL_STEP;
+        // Once the tyre slides, its force barely changes with wheel speed anymore. Linearising that would let the
+        // tyre force fling the wheel straight past the point where it rolls with the ground, so use the slope
+        // towards that point instead, whenever it is steeper
+        let rolling_angular_vel = contact_vel[0] / self.radius;
+        let angular_slip = angular_vel - rolling_angular_vel;
+        if angular_slip != 0.0 {
+            force_slope = force_slope.max(self.longitudinal_force.0 / angular_slip);
+        }
+
+        // Same for the sideways ground speed, so the vehicle can integrate the lateral force implicitly
+        const GROUND_SPEED_STEP: f32 = 0.01;
+        let sliding_contact_vel = [contact_vel[0], contact_vel[1] + GROUND_SPEED_STEP];
+        let (_, slip_angle_sliding) = self.calc_slip(sliding_contact_vel, angular_vel);
+        let (_, lateral_force_sliding) = self.tyre.combined_force(slip_ratio, slip_angle_sliding, load);
+        self.lateral_force_slope = (lateral_force_sliding - lateral_force).0 * friction_coefficient / GROUND_SPEED_STEP;
+
+        (self.longitudinal_force.torque_at(self.radius), force_slope * self.radius)
+    }
+
+    /// Returns the magnitude of the torque the surface puts on the wheel while rolling over it
+    fn calc_rolling_resistance_torque(&mut self) -> NewtonMetres {
+        let load = self.normal_load();
+        self.sinkage = self.surface.sinkage(load);
+        // A sunken tyre constantly has to climb out of its own rut
+        let coefficient = self.surface.rolling_resistance + self.sinkage / self.radius;
+        (load * coefficient).torque_at(self.radius)
     }
 
-    fn update_wheel_velocity(&mut self, delta_s: f32, torque: f32) {
+    /// accel_torque_slope is how much the tyre torque (included in torque) changes per rad/s of wheel speed.
+    /// resisting_torque is the magnitude of the brake and rolling resistance torque, which always opposes the rotation of the wheel
+    fn update_wheel_velocity(&mut self, delta_s: f32, torque: NewtonMetres, accel_torque_slope: f32, resisting_torque: NewtonMetres) {
         self.last_angular_vel = self.angular_vel;
 
-        let inertia = self.mass * (self.radius * self.radius) / 2.0;
-        let angular_accel = torque / inertia;
-        self.angular_vel += angular_accel * delta_s;
+        // The tyre is far too stiff to integrate explicitly, so its torque is linearised
+        // around the current wheel speed and integrated implicitly instead
+        let inertia = self.inertia();
+        let accel_torque_slope = accel_torque_slope.max(0.0);
+        self.effective_inertia = inertia + accel_torque_slope * delta_s;
+        self.angular_vel += RadPerSec(torque.0 / self.effective_inertia * delta_s);
+
+        // The brakes can bring the wheel to a stop, but never make it spin the other way.
+        // The tyre pushes back against them just the same, so they act on the same effective inertia
+        let resisting_delta = RadPerSec(resisting_torque.0 / self.effective_inertia * delta_s);
+        self.angular_vel -= resisting_delta.min(self.angular_vel.abs()) * self.angular_vel.signum();
+
+        // Report the tyre force at the new wheel speed, so the vehicle feels the same force the wheel did
+        let angular_vel_change = self.angular_vel - self.last_angular_vel;
+        self.longitudinal_force += Newtons(accel_torque_slope / self.radius * angular_vel_change.0);
 
-        self.wheel_speed = self.angular_vel * self.direction * self.radius;
+        self.wheel_speed = (self.angular_vel * self.direction).surface_speed(self.radius);
     }
 }
@@ -5,6 +5,10 @@
 // reference data available already, and it does
 // fairly well for now.
 
+use crate::units::{MetresPerSec, Newtons};
+
+use super::TyreModel;
+
 #[derive(Debug, Copy, Clone)]
 pub struct TyreData {
     /// Friction coefficient under no load
@@ -49,6 +53,11 @@ impl TyreData {
         coeff_a * coeff_b
     }
 
+    /// The force the tyre curve produces once the tyre is fully sliding
+    pub fn calculate_sliding_force(&self) -> f32 {
+        self.calculate_accel_force(std::f32::consts::PI * 0.5)
+    }
+
     pub fn calculate_accel_force(&self, slip_ratio: f32) -> f32 {
         let sign = slip_ratio.is_sign_positive() as u8 as f32 * 2.0 - 1.0;
         let slip_ratio = slip_ratio.abs().min(std::f32::consts::PI * 0.5);
@@ -58,6 +67,48 @@ impl TyreData {
         // arctan(slip_ratio * a) * b + a^-slip_ratio * c - c
         ((slip_ratio * a).atan() * b + a.powf(-slip_ratio) * c - c) * sign
     }
+
+    /// slip_angle:     radians
+    /// Uses the same curve as the longitudinal force, with the slip angle as input
+    pub fn calculate_lateral_force(&self, slip_angle: f32) -> f32 {
+        self.calculate_accel_force(slip_angle)
+    }
+
+    /// slip_angle:     radians
+    /// Returns (longitudinal force, lateral force). Both slips are combined into a single slip vector,
+    /// whose length is fed into the tyre curve. The resulting force is then split along the slip
+    /// direction, so the total force never leaves the friction circle, and braking or accelerating
+    /// in a corner reduces the available lateral grip.
+    pub fn calculate_combined_force(&self, slip_ratio: f32, slip_angle: f32) -> (f32, f32) {
+        let combined_slip = (slip_ratio * slip_ratio + slip_angle * slip_angle).sqrt();
+        if combined_slip == 0.0 {
+            return (0.0, 0.0);
+        }
+        let force = self.calculate_accel_force(combined_slip);
+        (force * slip_ratio / combined_slip, force * slip_angle / combined_slip)
+    }
+}
+
+// The tyre curve only determines the shape of the force. It is normalised so a fully sliding
+// tyre produces a force equal to the load, which then gets scaled by the friction coefficient.
+impl TyreModel for TyreData {
+    fn longitudinal_force(&self, slip_ratio: f32, load: Newtons) -> Newtons {
+        load * (self.calculate_accel_force(slip_ratio) / self.calculate_sliding_force())
+    }
+
+    fn lateral_force(&self, slip_angle: f32, load: Newtons) -> Newtons {
+        load * (self.calculate_lateral_force(slip_angle) / self.calculate_sliding_force())
+    }
+
+    fn friction_coeff(&self, sliding_vel: MetresPerSec, load: Newtons) -> f32 {
+        self.calculate_friction_coeff(sliding_vel.0, load.0)
+    }
+
+    fn combined_force(&self, slip_ratio: f32, slip_angle: f32, load: Newtons) -> (Newtons, Newtons) {
+        let (longitudinal, lateral) = self.calculate_combined_force(slip_ratio, slip_angle);
+        let scale = load / self.calculate_sliding_force();
+        (scale * longitudinal, scale * lateral)
+    }
 }
 
 /// sliding_vel:    m/s